@@ -0,0 +1,50 @@
+use axum_rest_api_models::ErrorResponse;
+use reqwest::StatusCode;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    /// 서버의 `AppError` 응답 (`{"error": ..., "request_id": ...}`)
+    #[error("{status}: {}", .error.error)]
+    Api { status: StatusCode, error: ErrorResponse },
+    /// `AppError` 형식이 아닌 실패 응답 (예: 본문 파싱 실패 422, 프록시 오류 페이지)
+    #[error("unexpected response {status}: {body}")]
+    UnexpectedResponse { status: StatusCode, body: String },
+    #[error("invalid base URL '{0}'")]
+    InvalidBaseUrl(String),
+    /// 요청 본문 인코딩 또는 응답 본문 디코딩 실패 (JSON, MessagePack, CBOR)
+    #[error("failed to encode or decode body: {0}")]
+    Codec(String),
+    /// 연결 실패, 타임아웃
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// WebSocket 연결 후의 오류 (업그레이드 전에 거절되면 `Api`)
+    #[error(transparent)]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+}
+
+// tungstenite::Error는 커서 다른 변형까지 Result가 커지지 않도록 Box에 담습니다.
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(e))
+    }
+}
+
+impl Error {
+    /// 서버가 응답한 경우의 상태 코드
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Api { status, .. } | Error::UnexpectedResponse { status, .. } => Some(*status),
+            Error::Http(e) => e.status(),
+            Error::InvalidBaseUrl(_) | Error::Codec(_) | Error::WebSocket(_) => None,
+        }
+    }
+
+    /// 서버 로그와 맞춰 볼 수 있는 X-Request-Id
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Error::Api { error, .. } => error.request_id.as_deref(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// GET /api/v1/users/events (Server-Sent Events) 읽기
+//
+// 연결이 끊기면 `last_event_id()`를 `V1::user_events`에 넘겨 이어 받습니다.
+// `StreamEvent::Resync`를 받으면 놓친 이벤트가 버퍼에 없으므로 사용자 목록을 다시 읽어야 합니다.
+use axum_rest_api_models::UserEvent;
+use reqwest::Response;
+use crate::Error;
+
+#[derive(Clone, Debug)]
+pub enum StreamEvent {
+    /// created, updated, deleted
+    User(UserEvent),
+    Resync { last_id: u64 },
+}
+
+pub struct UserEventStream {
+    res: Response,
+    buffer: String,
+    last_event_id: Option<u64>,
+}
+
+impl UserEventStream {
+    pub(crate) fn new(res: Response, last_event_id: Option<u64>) -> Self {
+        UserEventStream { res, buffer: String::new(), last_event_id }
+    }
+
+    /// 마지막으로 받은 이벤트 id (다시 연결할 때 Last-Event-ID)
+    pub fn last_event_id(&self) -> Option<u64> {
+        self.last_event_id
+    }
+
+    /// 다음 이벤트. 서버가 스트림을 닫으면 `Ok(None)`
+    pub async fn next(&mut self) -> Result<Option<StreamEvent>, Error> {
+        loop {
+            while let Some(end) = self.buffer.find("\n\n") {
+                let block: String = self.buffer.drain(..end + 2).collect();
+                if let Some(event) = self.parse(&block)? {
+                    return Ok(Some(event));
+                }
+            }
+            match self.res.chunk().await? {
+                Some(chunk) => self.buffer.push_str(&String::from_utf8_lossy(&chunk).replace("\r\n", "\n")),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// 블록 하나를 해석합니다. keep-alive 주석만 있는 블록은 None
+    fn parse(&mut self, block: &str) -> Result<Option<StreamEvent>, Error> {
+        let (mut id, mut event, mut data) = (None, None, Vec::new());
+        for line in block.lines() {
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "id" => id = value.parse::<u64>().ok(),
+                "event" => event = Some(value),
+                "data" => data.push(value),
+                _ => {}
+            }
+        }
+        if data.is_empty() {
+            return Ok(None);
+        }
+        if id.is_some() {
+            self.last_event_id = id;
+        }
+
+        let data = data.join("\n");
+        let decode_err = |e: serde_json::Error| Error::Codec(e.to_string());
+        match event {
+            Some("resync") => {
+                let value: serde_json::Value = serde_json::from_str(&data).map_err(decode_err)?;
+                let last_id = value["last_id"].as_u64().unwrap_or_default();
+                Ok(Some(StreamEvent::Resync { last_id }))
+            }
+            _ => Ok(Some(StreamEvent::User(serde_json::from_str(&data).map_err(decode_err)?))),
+        }
+    }
+}
@@ -0,0 +1,499 @@
+// axum-rest-api 클라이언트
+//
+// 서버와 같은 요청/응답 타입(axum-rest-api-models)을 쓰는 async 클라이언트입니다.
+// - 인증: 관리자 키(X-Admin-API-Key) 또는 개인 액세스 토큰(Authorization: Bearer)
+// - 오류: 실패 응답은 `Error::Api`로 `AppError` 본문(ErrorResponse)을 그대로 돌려줍니다.
+// - 형식: 기본은 JSON. `ClientBuilder::format`으로 MessagePack/CBOR 본문을 주고받을 수 있습니다.
+// - 재시도: 연결 실패와 429는 모든 요청, 502/503/504는 GET/DELETE만 지수 백오프로 재시도합니다.
+//   429/503의 Retry-After 헤더가 있으면 그 시간(최대 `max_backoff`)을 기다립니다.
+//
+// 서버의 OpenAPI 문서(`ApiDoc`, `ApiDocV1`)에 있는 operation은 모두 `OPERATIONS`, `OPERATIONS_V1`에 있어야 하며,
+// 서버 쪽 tests/client.rs 가 문서와 비교하고 실제 서버에 호출해 봅니다.
+// GET /ws 는 `Client::websocket`으로 구독합니다 (`WsSubscription`).
+// `Client`의 users/items/tokens 메서드는 폐기 예정인 예전 경로를 부릅니다. 새 코드는 `client.v1()`을 쓰세요.
+//
+// 사용 예:
+//   let client = Client::builder("http://localhost:3000").admin_key("...").build()?;
+//   let token = client.create_token(1, &CreateTokenRequest { name: "ci".into(), scopes: vec!["tokens".into()] }).await?;
+//   let me = client.with_auth(Auth::Bearer(token.token));
+//   let tokens = me.list_tokens(1).await?;
+mod error;
+mod events;
+mod ws;
+
+pub use axum_rest_api_models as models;
+pub use axum_rest_api_models::Format;
+pub use error::Error;
+pub use events::{StreamEvent, UserEventStream};
+pub use ws::WsSubscription;
+
+use axum_rest_api_models::{
+    ApiToken, BodyItem, CreateTokenRequest, CreateUserRequest, CreatedApiToken, ErrorResponse, OidcLoginResponse,
+    SlowEvent, UpdateUserRequest, User, UserItem,
+};
+use rand::Rng;
+use reqwest::{header, Method, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
+/// 이 클라이언트가 지원하는 operation (OpenAPI 문서의 method, path)
+pub const OPERATIONS: &[(&str, &str)] = &[
+    ("get", "/health"),
+    ("get", "/ready"),
+    ("post", "/create-user"),
+    ("post", "/create-user-db"),
+    ("get", "/users"),
+    ("get", "/axum-users"),
+    ("delete", "/delete-user/{id}"),
+    ("get", "/item/{id}"),
+    ("post", "/add-item"),
+    ("get", "/admin/get_app_state"),
+    ("get", "/admin/slow-events"),
+    ("post", "/users/{id}/tokens"),
+    ("get", "/users/{id}/tokens"),
+    ("delete", "/users/{id}/tokens/{token_id}"),
+    ("get", "/auth/oidc/login"),
+    ("get", "/auth/oidc/callback"),
+    ("get", "/ws"),
+];
+
+/// `Client::v1()`이 지원하는 /api/v1 operation. 버전 없는 경로(health, admin, OIDC)는 `Client`에 있습니다.
+pub const OPERATIONS_V1: &[(&str, &str)] = &[
+    ("get", "/health"),
+    ("get", "/ready"),
+    ("get", "/api/v1/users"),
+    ("post", "/api/v1/users"),
+    ("get", "/api/v1/users/{id}"),
+    ("patch", "/api/v1/users/{id}"),
+    ("delete", "/api/v1/users/{id}"),
+    ("get", "/api/v1/users/events"),
+    ("get", "/api/v1/items/{id}"),
+    ("post", "/api/v1/items"),
+    ("post", "/api/v1/users/{id}/tokens"),
+    ("get", "/api/v1/users/{id}/tokens"),
+    ("delete", "/api/v1/users/{id}/tokens/{token_id}"),
+    ("get", "/admin/get_app_state"),
+    ("get", "/admin/slow-events"),
+    ("get", "/auth/oidc/login"),
+    ("get", "/auth/oidc/callback"),
+    ("get", "/ws"),
+];
+
+#[derive(Clone, Debug, Default)]
+pub enum Auth {
+    #[default]
+    None,
+    /// X-Admin-API-Key
+    AdminKey(String),
+    /// 개인 액세스 토큰 (arat_...)
+    Bearer(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// 첫 요청 이후 최대 재시도 횟수. 0이면 재시도하지 않습니다.
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        RetryPolicy { max_retries: 0, ..RetryPolicy::default() }
+    }
+
+    /// `attempt`(0부터) 번째 재시도 전 대기 시간. 지수 백오프에 ±20% jitter
+    fn backoff(&self, attempt: u32) -> Duration {
+        let base = self.initial_backoff.saturating_mul(2u32.saturating_pow(attempt)).min(self.max_backoff);
+        base.mul_f64(rand::rng().random_range(0.8..1.2)).min(self.max_backoff)
+    }
+}
+
+pub struct ClientBuilder {
+    base_url: String,
+    auth: Auth,
+    retry: RetryPolicy,
+    timeout: Duration,
+    user_agent: String,
+    format: Format,
+}
+
+impl ClientBuilder {
+    pub fn admin_key(mut self, key: impl Into<String>) -> Self {
+        self.auth = Auth::AdminKey(key.into());
+        self
+    }
+
+    pub fn bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.auth = Auth::Bearer(token.into());
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 요청 하나(재시도 제외)의 제한 시간. 기본값 30초
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// 요청 본문과 (지원하는 경로의) 응답 본문 형식. 기본값 JSON
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn build(self) -> Result<Client, Error> {
+        let base_url = self.base_url.trim_end_matches('/').to_string();
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+            return Err(Error::InvalidBaseUrl(self.base_url));
+        }
+        let http = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .user_agent(self.user_agent)
+            // /auth/oidc/login 의 303을 따라가지 않고 Location을 돌려주기 위해
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+        Ok(Client { http, base_url, auth: self.auth, retry: self.retry, format: self.format })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    auth: Auth,
+    retry: RetryPolicy,
+    format: Format,
+}
+
+impl Client {
+    pub fn builder(base_url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            base_url: base_url.into(),
+            auth: Auth::None,
+            retry: RetryPolicy::default(),
+            timeout: Duration::from_secs(30),
+            user_agent: concat!("axum-rest-api-client/", env!("CARGO_PKG_VERSION")).to_string(),
+            format: Format::Json,
+        }
+    }
+
+    /// 같은 연결 풀을 쓰면서 인증만 바꾼 클라이언트
+    pub fn with_auth(&self, auth: Auth) -> Client {
+        Client { auth, ..self.clone() }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// /api/v1 리소스 경로
+    pub fn v1(&self) -> V1<'_> {
+        V1 { client: self }
+    }
+
+    //-- health ----------------
+
+    /// GET /health
+    pub async fn health(&self) -> Result<serde_json::Value, Error> {
+        self.decode(self.request(Method::GET, "/health")).await
+    }
+
+    /// GET /ready. 종료 중(503)이면 `Error::Api`
+    pub async fn ready(&self) -> Result<serde_json::Value, Error> {
+        self.decode(self.request(Method::GET, "/ready")).await
+    }
+
+    //-- users ----------------
+
+    /// POST /create-user (저장하지 않는 테스트용 엔드포인트)
+    pub async fn create_user(&self) -> Result<String, Error> {
+        self.text(self.request(Method::POST, "/create-user")).await
+    }
+
+    /// POST /create-user-db
+    pub async fn create_user_db(&self, user: &CreateUserRequest) -> Result<serde_json::Value, Error> {
+        self.decode(self.body(self.request(Method::POST, "/create-user-db"), user)?).await
+    }
+
+    /// GET /users (고정 목록)
+    pub async fn list_users(&self) -> Result<Vec<User>, Error> {
+        self.decode(self.request(Method::GET, "/users")).await
+    }
+
+    /// GET /axum-users
+    pub async fn list_users_db(&self) -> Result<Vec<User>, Error> {
+        self.decode(self.request(Method::GET, "/axum-users")).await
+    }
+
+    /// DELETE /delete-user/{id}
+    pub async fn delete_user(&self, id: i32) -> Result<UserItem, Error> {
+        self.decode(self.request(Method::DELETE, &format!("/delete-user/{}", id))).await
+    }
+
+    //-- items ----------------
+
+    /// GET /item/{id}?number=
+    pub async fn show_item(&self, id: i32, number: u32) -> Result<String, Error> {
+        self.text(self.request(Method::GET, &format!("/item/{}", id)).query(&[("number", number)])).await
+    }
+
+    /// POST /add-item
+    pub async fn add_item(&self, item: &BodyItem) -> Result<String, Error> {
+        self.text(self.body(self.request(Method::POST, "/add-item"), item)?).await
+    }
+
+    //-- admin ----------------
+
+    /// GET /admin/get_app_state
+    pub async fn get_app_state(&self) -> Result<serde_json::Value, Error> {
+        self.decode(self.request(Method::GET, "/admin/get_app_state")).await
+    }
+
+    /// GET /admin/slow-events?limit=
+    pub async fn list_slow_events(&self, limit: Option<usize>) -> Result<Vec<SlowEvent>, Error> {
+        let mut request = self.request(Method::GET, "/admin/slow-events");
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)]);
+        }
+        self.decode(request).await
+    }
+
+    //-- personal access tokens ----------------
+
+    /// POST /users/{id}/tokens. 반환값의 `token` 원문은 이때 한 번만 받을 수 있습니다.
+    pub async fn create_token(&self, user_id: i32, req: &CreateTokenRequest) -> Result<CreatedApiToken, Error> {
+        self.decode(self.body(self.request(Method::POST, &format!("/users/{}/tokens", user_id)), req)?).await
+    }
+
+    /// GET /users/{id}/tokens
+    pub async fn list_tokens(&self, user_id: i32) -> Result<Vec<ApiToken>, Error> {
+        self.decode(self.request(Method::GET, &format!("/users/{}/tokens", user_id))).await
+    }
+
+    /// DELETE /users/{id}/tokens/{token_id}
+    pub async fn revoke_token(&self, user_id: i32, token_id: i32) -> Result<(), Error> {
+        self.no_content(self.request(Method::DELETE, &format!("/users/{}/tokens/{}", user_id, token_id))).await
+    }
+
+    //-- OIDC ----------------
+
+    /// GET /auth/oidc/login. 브라우저를 보낼 identity provider URL을 돌려줍니다.
+    pub async fn oidc_login_url(&self) -> Result<String, Error> {
+        let res = self.send(self.request(Method::GET, "/auth/oidc/login")).await?;
+        match res.headers().get(header::LOCATION).and_then(|v| v.to_str().ok()) {
+            Some(location) => Ok(location.to_string()),
+            None => {
+                let status = res.status();
+                Err(Error::UnexpectedResponse { status, body: res.text().await.unwrap_or_default() })
+            }
+        }
+    }
+
+    /// GET /auth/oidc/callback?code=&state=
+    pub async fn oidc_callback(&self, code: &str, state: &str) -> Result<OidcLoginResponse, Error> {
+        self.decode(self.request(Method::GET, "/auth/oidc/callback").query(&[("code", code), ("state", state)]))
+            .await
+    }
+
+    //-- 공통 ----------------
+
+    async fn no_content(&self, request: RequestBuilder) -> Result<(), Error> {
+        self.send(request).await.map(|_| ())
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let mut request = self.http.request(method, format!("{}{}", self.base_url, path));
+        if self.format != Format::Json {
+            // 협상하지 않는 경로도 406 없이 JSON으로 받을 수 있게 JSON을 낮은 우선순위로 허용합니다.
+            request = request.header(header::ACCEPT, format!("{}, application/json;q=0.5", self.format.content_type()));
+        }
+        match &self.auth {
+            Auth::None => request,
+            Auth::AdminKey(key) => request.header("X-Admin-API-Key", key),
+            Auth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+
+    /// 요청 본문을 `format`으로 인코딩합니다.
+    fn body<T: Serialize>(&self, request: RequestBuilder, value: &T) -> Result<RequestBuilder, Error> {
+        let bytes = self.format.encode(value).map_err(Error::Codec)?;
+        Ok(request.header(header::CONTENT_TYPE, self.format.content_type()).body(bytes))
+    }
+
+    /// 응답의 Content-Type에 맞게 디코딩합니다. 협상하지 않는 경로(health 등)는 `format`과 관계없이 JSON입니다.
+    async fn decode<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, Error> {
+        let res = self.send(request).await?;
+        let format = res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(Format::from_media_type)
+            .unwrap_or(Format::Json);
+        format.decode(&res.bytes().await?).map_err(Error::Codec)
+    }
+
+    async fn text(&self, request: RequestBuilder) -> Result<String, Error> {
+        Ok(self.send(request).await?.text().await?)
+    }
+
+    /// 재시도 정책에 따라 보내고, 2xx/3xx가 아니면 `Error`로 바꿉니다.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, Error> {
+        let mut attempt = 0;
+        loop {
+            // 본문은 모두 메모리에 있으므로 항상 복제할 수 있습니다.
+            let (client, req) = request.try_clone().expect("request body is not cloneable").build_split();
+            let req = req?;
+            let idempotent = matches!(*req.method(), Method::GET | Method::HEAD | Method::DELETE | Method::OPTIONS);
+            let result = client.execute(req).await;
+
+            let retry_after = match &result {
+                Ok(res) => match res.status() {
+                    StatusCode::TOO_MANY_REQUESTS => Some(retry_after(res)),
+                    StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+                        if idempotent =>
+                    {
+                        Some(retry_after(res))
+                    }
+                    _ => None,
+                },
+                // 연결하지 못한 요청은 서버에 닿지 않았으므로 POST도 다시 보냅니다.
+                Err(e) if e.is_connect() || (idempotent && e.is_timeout()) => Some(None),
+                Err(_) => None,
+            };
+
+            match retry_after {
+                Some(hint) if attempt < self.retry.max_retries => {
+                    let wait = hint.map(|h| h.min(self.retry.max_backoff)).unwrap_or_else(|| self.retry.backoff(attempt));
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                _ => return check(result?).await,
+            }
+        }
+    }
+}
+
+/// `Client::v1()`. 인증, 재시도, 연결 풀은 `Client`와 같습니다.
+#[derive(Clone, Copy, Debug)]
+pub struct V1<'a> {
+    client: &'a Client,
+}
+
+impl V1<'_> {
+    //-- users ----------------
+
+    /// GET /api/v1/users
+    pub async fn list_users(&self) -> Result<Vec<User>, Error> {
+        self.client.decode(self.client.request(Method::GET, "/api/v1/users")).await
+    }
+
+    /// POST /api/v1/users
+    pub async fn create_user(&self, user: &CreateUserRequest) -> Result<User, Error> {
+        self.client.decode(self.client.body(self.client.request(Method::POST, "/api/v1/users"), user)?).await
+    }
+
+    /// GET /api/v1/users/{id}
+    pub async fn get_user(&self, id: i32) -> Result<User, Error> {
+        self.client.decode(self.client.request(Method::GET, &format!("/api/v1/users/{}", id))).await
+    }
+
+    /// PATCH /api/v1/users/{id}
+    pub async fn update_user(&self, id: i32, changes: &UpdateUserRequest) -> Result<User, Error> {
+        let request = self.client.request(Method::PATCH, &format!("/api/v1/users/{}", id));
+        self.client.decode(self.client.body(request, changes)?).await
+    }
+
+    /// GET /api/v1/users/events. `last_event_id`가 있으면 그 다음 이벤트부터 받습니다.
+    /// 관리자 키 또는 `users:read` 스코프 토큰이 필요합니다.
+    pub async fn user_events(&self, last_event_id: Option<u64>) -> Result<UserEventStream, Error> {
+        let mut request = self
+            .client
+            .request(Method::GET, "/api/v1/users/events")
+            .header(header::ACCEPT, "text/event-stream")
+            // 스트림은 끝나지 않으므로 클라이언트 전체 제한 시간(`timeout`)을 쓰지 않습니다.
+            .timeout(Duration::from_secs(86400 * 365));
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id.to_string());
+        }
+        let res = self.client.send(request).await?;
+        Ok(UserEventStream::new(res, last_event_id))
+    }
+
+    /// DELETE /api/v1/users/{id}. 사용자의 토큰도 함께 지워집니다.
+    pub async fn delete_user(&self, id: i32) -> Result<(), Error> {
+        self.client.no_content(self.client.request(Method::DELETE, &format!("/api/v1/users/{}", id))).await
+    }
+
+    //-- items ----------------
+
+    /// GET /api/v1/items/{id}?number=
+    pub async fn get_item(&self, id: i32, number: u32) -> Result<String, Error> {
+        let request = self.client.request(Method::GET, &format!("/api/v1/items/{}", id)).query(&[("number", number)]);
+        self.client.text(request).await
+    }
+
+    /// POST /api/v1/items
+    pub async fn create_item(&self, item: &BodyItem) -> Result<BodyItem, Error> {
+        self.client.decode(self.client.body(self.client.request(Method::POST, "/api/v1/items"), item)?).await
+    }
+
+    //-- personal access tokens ----------------
+
+    /// POST /api/v1/users/{id}/tokens. 반환값의 `token` 원문은 이때 한 번만 받을 수 있습니다.
+    pub async fn create_token(&self, user_id: i32, req: &CreateTokenRequest) -> Result<CreatedApiToken, Error> {
+        let request = self.client.request(Method::POST, &format!("/api/v1/users/{}/tokens", user_id));
+        let request = self.client.body(request, req)?;
+        self.client.decode(request).await
+    }
+
+    /// GET /api/v1/users/{id}/tokens
+    pub async fn list_tokens(&self, user_id: i32) -> Result<Vec<ApiToken>, Error> {
+        self.client.decode(self.client.request(Method::GET, &format!("/api/v1/users/{}/tokens", user_id))).await
+    }
+
+    /// DELETE /api/v1/users/{id}/tokens/{token_id}
+    pub async fn revoke_token(&self, user_id: i32, token_id: i32) -> Result<(), Error> {
+        let path = format!("/api/v1/users/{}/tokens/{}", user_id, token_id);
+        self.client.no_content(self.client.request(Method::DELETE, &path)).await
+    }
+}
+
+fn retry_after(res: &Response) -> Option<Duration> {
+    res.headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+async fn check(res: Response) -> Result<Response, Error> {
+    let status = res.status();
+    if status.is_success() || status.is_redirection() {
+        return Ok(res);
+    }
+    let body = res.text().await?;
+    match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(error) => Err(Error::Api { status, error }),
+        Err(_) => Err(Error::UnexpectedResponse { status, body }),
+    }
+}
@@ -0,0 +1,104 @@
+// GET /ws (WebSocket) 구독
+//
+// 연결할 때 `Client`의 인증 헤더를 그대로 보냅니다. 서버의 ping에는 읽는 동안 자동으로 pong을 보내므로,
+// 오래 `next()`를 부르지 않으면 서버가 끊을 수 있습니다. `WsMessage::Lagged`를 받으면 이벤트를 놓친 것이므로
+// REST로 목록을 다시 읽으세요.
+use axum_rest_api_models::{ErrorResponse, WsMessage, WsRequest, WsTopic};
+use futures_util::{SinkExt, StreamExt};
+use reqwest::StatusCode;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest, http::HeaderValue, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use crate::{Auth, Client, Error};
+
+pub struct WsSubscription {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl Client {
+    /// GET /ws. 연결한 뒤 `topics`를 구독합니다 (응답은 `WsMessage::Subscribed`).
+    pub async fn websocket(&self, topics: &[WsTopic]) -> Result<WsSubscription, Error> {
+        let url = format!("ws{}/ws", self.base_url.strip_prefix("http").unwrap_or(&self.base_url));
+        let mut request = url.as_str().into_client_request().map_err(|_| Error::InvalidBaseUrl(url.clone()))?;
+        let header = |value: &str| HeaderValue::from_str(value).map_err(|e| Error::Codec(e.to_string()));
+        match &self.auth {
+            Auth::None => {}
+            Auth::AdminKey(key) => {
+                request.headers_mut().insert("X-Admin-API-Key", header(key)?);
+            }
+            Auth::Bearer(token) => {
+                request.headers_mut().insert("Authorization", header(&format!("Bearer {}", token))?);
+            }
+        }
+
+        let (socket, _) = tokio_tungstenite::connect_async(request).await.map_err(handshake_error)?;
+        let mut subscription = WsSubscription { socket };
+        if !topics.is_empty() {
+            subscription.subscribe(topics).await?;
+        }
+        Ok(subscription)
+    }
+}
+
+/// 업그레이드 전에 거절된 경우(401, 503 등) 다른 요청처럼 `Error::Api`로 바꿉니다.
+fn handshake_error(error: tungstenite::Error) -> Error {
+    let tungstenite::Error::Http(res) = error else {
+        return error.into();
+    };
+    let status = StatusCode::from_u16(res.status().as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let body = res.body().clone().unwrap_or_default();
+    let body = dechunk(&body).unwrap_or(body);
+    match serde_json::from_slice::<ErrorResponse>(&body) {
+        Ok(error) => Error::Api { status, error },
+        Err(_) => Error::UnexpectedResponse { status, body: String::from_utf8_lossy(&body).into_owned() },
+    }
+}
+
+/// tungstenite는 거절 응답 본문의 chunked 인코딩을 풀지 않으므로 직접 풉니다. chunked가 아니면 None
+fn dechunk(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body.windows(2).position(|w| w == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?.split(';').next()?.trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+impl WsSubscription {
+    pub async fn subscribe(&mut self, topics: &[WsTopic]) -> Result<(), Error> {
+        self.send(&WsRequest::Subscribe { topics: topics.to_vec() }).await
+    }
+
+    pub async fn unsubscribe(&mut self, topics: &[WsTopic]) -> Result<(), Error> {
+        self.send(&WsRequest::Unsubscribe { topics: topics.to_vec() }).await
+    }
+
+    async fn send(&mut self, request: &WsRequest) -> Result<(), Error> {
+        let text = serde_json::to_string(request).map_err(|e| Error::Codec(e.to_string()))?;
+        Ok(self.socket.send(Message::Text(text)).await?)
+    }
+
+    /// 다음 메시지. 서버가 연결을 닫으면 `Ok(None)`
+    pub async fn next(&mut self) -> Result<Option<WsMessage>, Error> {
+        while let Some(message) = self.socket.next().await {
+            match message? {
+                Message::Text(text) => {
+                    return serde_json::from_str(&text).map(Some).map_err(|e| Error::Codec(e.to_string()));
+                }
+                Message::Close(_) => return Ok(None),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn close(mut self) -> Result<(), Error> {
+        Ok(self.socket.close(None).await?)
+    }
+}
@@ -0,0 +1,60 @@
+// 본문 직렬화 형식 (JSON, MessagePack, CBOR)
+//
+// 서버는 Accept/Content-Type으로 형식을 고르고, 클라이언트는 같은 형식으로 요청하고 디코딩합니다.
+// MessagePack은 필드 이름을 넣은 map으로 인코딩하므로(`to_vec_named`) JSON과 같은 모양입니다.
+use serde::{Serialize, de::DeserializeOwned};
+
+pub const JSON: &str = "application/json";
+pub const MSGPACK: &str = "application/msgpack";
+pub const CBOR: &str = "application/cbor";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Json,
+    MsgPack,
+    Cbor,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Json, Format::MsgPack, Format::Cbor];
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => JSON,
+            Format::MsgPack => MSGPACK,
+            Format::Cbor => CBOR,
+        }
+    }
+
+    /// 파라미터(`; charset=utf-8`)를 뺀 media type. 예전 MessagePack 이름도 받습니다.
+    pub fn from_media_type(media_type: &str) -> Option<Format> {
+        let media_type = media_type.split(';').next().unwrap_or_default().trim();
+        match media_type.to_ascii_lowercase().as_str() {
+            JSON => Some(Format::Json),
+            MSGPACK | "application/x-msgpack" | "application/vnd.msgpack" => Some(Format::MsgPack),
+            CBOR => Some(Format::Cbor),
+            _ => None,
+        }
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Format::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            Format::MsgPack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            Format::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Format::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            Format::MsgPack => rmp_serde::from_slice(bytes).map_err(|e| e.to_string()),
+            Format::Cbor => ciborium::from_reader(bytes).map_err(|e| e.to_string()),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+// axum-rest-api 요청/응답 타입
+//
+// 서버(`axum_rest_api::models`)와 클라이언트(axum-rest-api-client)가 같은 타입을 씁니다.
+// 필드를 바꾸면 OpenAPI 문서와 양쪽이 함께 바뀝니다.
+pub mod format;
+
+pub use format::Format;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+}
+
+/// `AppError` 응답 본문
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct ErrorResponse {
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct UserItem {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct Page {
+    /// 없으면 1
+    pub number: Option<u32>,
+}
+
+impl Page {
+    pub fn number(&self) -> u32 {
+        self.number.unwrap_or(1)
+    }
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct BodyItem {
+    pub title: String,
+}
+
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct CreateUserRequest {
+    pub name: String,
+    pub email: String,
+}
+
+/// PATCH /api/v1/users/{id}. 없는 필드는 바꾸지 않습니다.
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug, Default)]
+pub struct UpdateUserRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct CreateTokenRequest {
+    pub name: String,
+    /// 예: ["users:read", "tokens"]
+    pub scopes: Vec<String>,
+}
+
+/// 토큰 목록 조회 결과. 토큰 원문과 해시는 포함하지 않습니다.
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct ApiToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: i64,
+    pub last_used_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+}
+
+/// 토큰 생성 응답. `token` 원문은 이 응답에서 한 번만 보여 줍니다.
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct CreatedApiToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub token: String,
+    pub created_at: i64,
+}
+
+/// OIDC 로그인 성공 응답. 매핑된 사용자와 새로 발급한 개인 액세스 토큰을 돌려줍니다.
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct OidcLoginResponse {
+    pub user: User,
+    pub token: CreatedApiToken,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SlowEventKind {
+    Query,
+    Request,
+}
+
+/// 느린 쿼리 / 느린 요청 기록
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct SlowEvent {
+    pub kind: SlowEventKind,
+    /// 쿼리 이름 또는 "GET /axum-users"
+    pub name: String,
+    /// 값이 가려진 SQL 문 (쿼리인 경우)
+    pub sql: Option<String>,
+    pub duration_ms: u64,
+    /// unix epoch 초
+    pub at: i64,
+    pub request_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UserChange {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl UserChange {
+    /// SSE `event` 이름 (serde 표현과 같음)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UserChange::Created => "created",
+            UserChange::Updated => "updated",
+            UserChange::Deleted => "deleted",
+        }
+    }
+}
+
+/// GET /api/v1/users/events 의 SSE `data`. `id`는 SSE 이벤트 id(Last-Event-ID)와 같습니다.
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct UserEvent {
+    pub id: u64,
+    pub change: UserChange,
+    /// 삭제된 경우 삭제 직전의 값
+    pub user: User,
+}
+
+#[derive(Deserialize, Serialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemChange {
+    Created,
+}
+
+/// WebSocket `items` 토픽의 이벤트
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+pub struct ItemEvent {
+    pub id: u64,
+    pub change: ItemChange,
+    pub item: BodyItem,
+}
+
+/// GET /ws 에서 구독할 수 있는 토픽
+#[derive(Deserialize, Serialize, ToSchema, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum WsTopic {
+    /// `users:read` 스코프 필요
+    Users,
+    Items,
+}
+
+/// 클라이언트가 보내는 WebSocket 텍스트 메시지 (JSON)
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WsRequest {
+    Subscribe { topics: Vec<WsTopic> },
+    Unsubscribe { topics: Vec<WsTopic> },
+}
+
+/// 서버가 보내는 WebSocket 텍스트 메시지 (JSON)
+#[derive(Deserialize, Serialize, ToSchema, Clone, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WsMessage {
+    /// subscribe/unsubscribe 결과. 지금 구독 중인 토픽 전체
+    Subscribed { topics: Vec<WsTopic> },
+    User { event: UserEvent },
+    Item { event: ItemEvent },
+    /// 클라이언트가 느려 전송 대기열이 넘쳐 버린 이벤트 수. REST로 목록을 다시 읽어야 합니다.
+    Lagged { topic: WsTopic, missed: u64 },
+    Error { error: String },
+}
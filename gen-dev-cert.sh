@@ -0,0 +1,51 @@
+#!/bin/bash
+# 로컬 테스트용 자체 서명 인증서 생성 (localhost, 127.0.0.1, ::1)
+# mTLS 테스트용 클라이언트 CA와, 그 CA가 서명한 관리자 클라이언트 인증서(CN=ops-admin)도 함께 만듭니다.
+#   ./gen-dev-cert.sh [출력 디렉터리]   (기본값 ./certs)
+#
+# .env:
+#   TLS_CERT_PATH=./certs/cert.pem
+#   TLS_KEY_PATH=./certs/key.pem
+#   TLS_CLIENT_CA_PATH=./certs/client-ca.pem
+#   ADMIN_REQUIRE_CLIENT_CERT=true
+#
+# 확인:
+#   curl --cacert ./certs/cert.pem https://localhost:3000/health
+#   curl --cacert ./certs/cert.pem --cert ./certs/client.pem --key ./certs/client-key.pem \
+#        -H "X-Admin-API-Key: $ADMIN_API_KEY" https://localhost:3000/admin/get_app_state
+#   kill -HUP <pid>   # 인증서를 바꾼 뒤 즉시 다시 읽게 하기
+set -euo pipefail
+
+OUT_DIR="${1:-./certs}"
+mkdir -p "$OUT_DIR"
+
+openssl req -x509 -newkey ec -pkeyopt ec_paramgen_curve:prime256v1 -nodes \
+    -days 30 \
+    -subj "/CN=localhost" \
+    -addext "subjectAltName=DNS:localhost,IP:127.0.0.1,IP:::1" \
+    -keyout "$OUT_DIR/key.pem" \
+    -out "$OUT_DIR/cert.pem"
+
+# 클라이언트 CA (이미 있으면 그대로 사용해 서버 인증서만 갱신할 수 있게 합니다)
+if [ ! -f "$OUT_DIR/client-ca.pem" ]; then
+    openssl req -x509 -newkey ec -pkeyopt ec_paramgen_curve:prime256v1 -nodes \
+        -days 365 \
+        -subj "/CN=axum-rest-api dev client CA" \
+        -addext "basicConstraints=critical,CA:TRUE" \
+        -addext "keyUsage=critical,keyCertSign" \
+        -keyout "$OUT_DIR/client-ca-key.pem" \
+        -out "$OUT_DIR/client-ca.pem"
+fi
+
+openssl req -new -newkey ec -pkeyopt ec_paramgen_curve:prime256v1 -nodes \
+    -subj "/CN=ops-admin" \
+    -keyout "$OUT_DIR/client-key.pem" \
+    -out "$OUT_DIR/client.csr"
+openssl x509 -req -in "$OUT_DIR/client.csr" -days 30 \
+    -CA "$OUT_DIR/client-ca.pem" -CAkey "$OUT_DIR/client-ca-key.pem" -CAcreateserial \
+    -extfile <(printf "extendedKeyUsage=clientAuth") \
+    -out "$OUT_DIR/client.pem"
+rm -f "$OUT_DIR/client.csr"
+
+echo "generated $OUT_DIR/cert.pem, $OUT_DIR/key.pem (server)"
+echo "generated $OUT_DIR/client-ca.pem (client CA), $OUT_DIR/client.pem, $OUT_DIR/client-key.pem (CN=ops-admin)"
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS axum_users (
+    id INT AUTO_INCREMENT PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    email VARCHAR(255) NOT NULL
+);
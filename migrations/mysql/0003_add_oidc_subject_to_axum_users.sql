@@ -0,0 +1,2 @@
+-- OIDC ID 토큰의 sub(subject)와 axum_users 행을 연결합니다.
+ALTER TABLE axum_users ADD COLUMN oidc_subject VARCHAR(255) NULL UNIQUE;
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS axum_users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL
+);
@@ -0,0 +1,14 @@
+-- 개인 액세스 토큰 (personal access token)
+-- 원본 토큰은 저장하지 않고 SHA-256 해시만 저장합니다. 시각은 unix epoch 초 단위입니다.
+CREATE TABLE IF NOT EXISTS axum_api_tokens (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    token_prefix TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    last_used_at BIGINT NULL,
+    revoked_at BIGINT NULL,
+    FOREIGN KEY (user_id) REFERENCES axum_users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,4 @@
+-- OIDC ID 토큰의 sub(subject)와 axum_users 행을 연결합니다.
+-- SQLite는 ADD COLUMN에 UNIQUE를 붙일 수 없으므로 인덱스로 만듭니다.
+ALTER TABLE axum_users ADD COLUMN oidc_subject TEXT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS axum_users_oidc_subject ON axum_users (oidc_subject);
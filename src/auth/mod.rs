@@ -0,0 +1,164 @@
+pub mod oidc;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::Row;
+use crate::db::{self, DbPool};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::metrics::observe_query;
+use crate::models::CreatedApiToken;
+
+// 개인 액세스 토큰(PAT) 접두어. 로그나 설정 파일에서 토큰을 쉽게 식별하기 위해 사용합니다.
+pub const TOKEN_PREFIX: &str = "arat_";
+
+pub const SCOPE_USERS_READ: &str = "users:read";
+pub const SCOPE_USERS_WRITE: &str = "users:write";
+pub const SCOPE_TOKENS: &str = "tokens";
+pub const SCOPE_ADMIN: &str = "admin";
+
+pub const KNOWN_SCOPES: &[&str] = &[SCOPE_USERS_READ, SCOPE_USERS_WRITE, SCOPE_TOKENS, SCOPE_ADMIN];
+
+/// 인증된 호출자. `auth_middleware`가 request extensions에 넣어 줍니다.
+#[derive(Clone, Debug)]
+pub enum Principal {
+    /// `X-Admin-API-Key` 헤더로 인증된 관리자
+    Admin,
+    /// `Authorization: Bearer <token>` 으로 인증된 사용자
+    User {
+        user_id: i32,
+        token_id: i32,
+        scopes: Vec<String>,
+    },
+}
+
+impl Principal {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        match self {
+            Principal::Admin => true,
+            Principal::User { scopes, .. } => scopes.iter().any(|s| s == scope),
+        }
+    }
+
+    /// 관리자이거나, `tokens` 스코프를 가진 본인인 경우에만 토큰을 관리할 수 있습니다.
+    pub fn can_manage_tokens_of(&self, user_id: i32) -> bool {
+        match self {
+            Principal::Admin => true,
+            Principal::User { user_id: own_id, .. } => {
+                *own_id == user_id && self.has_scope(SCOPE_TOKENS)
+            }
+        }
+    }
+}
+
+/// 새 토큰 원문을 생성합니다. 원문은 생성 응답에서 한 번만 보여 줍니다.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    format!("{}{}", TOKEN_PREFIX, hex::encode(bytes))
+}
+
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// 목록 화면에서 토큰을 구분할 수 있도록 접두어 + 앞 4글자만 보관합니다.
+pub fn display_prefix(token: &str) -> String {
+    token.chars().take(TOKEN_PREFIX.len() + 4).collect()
+}
+
+pub fn parse_scopes(scopes: &str) -> Vec<String> {
+    scopes
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+pub fn now_epoch() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Bearer 토큰을 검증하고, 성공하면 `last_used_at`을 갱신합니다.
+/// 폐기(revoke)되었거나 존재하지 않는 토큰이면 `Ok(None)`을 반환합니다.
+pub async fn authenticate_token(
+    db_pool: &DbPool,
+    token: &str,
+) -> Result<Option<Principal>, sqlx::Error> {
+    if !token.starts_with(TOKEN_PREFIX) {
+        return Ok(None);
+    }
+
+    let row = observe_query(
+        "find_token",
+        sqlx::query(
+            "SELECT id, user_id, scopes FROM axum_api_tokens WHERE token_hash = ? AND revoked_at IS NULL",
+        )
+        .bind(hash_token(token)),
+        |q| q.fetch_optional(db_pool),
+    )
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let token_id: i32 = row.try_get("id")?;
+    let user_id: i32 = row.try_get("user_id")?;
+    let scopes: String = row.try_get("scopes")?;
+
+    observe_query(
+        "touch_token",
+        sqlx::query("UPDATE axum_api_tokens SET last_used_at = ? WHERE id = ?")
+            .bind(now_epoch())
+            .bind(token_id),
+        |q| q.execute(db_pool),
+    )
+    .await?;
+
+    Ok(Some(Principal::User {
+        user_id,
+        token_id,
+        scopes: parse_scopes(&scopes),
+    }))
+}
+
+/// 새 토큰을 발급하고 해시만 저장합니다. 반환값의 `token` 원문은 호출자에게 한 번만 전달해야 합니다.
+pub async fn issue_token(
+    db_pool: &DbPool,
+    user_id: i32,
+    name: &str,
+    scopes: &[String],
+) -> Result<CreatedApiToken, sqlx::Error> {
+    let token = generate_token();
+    let created_at = now_epoch();
+
+    let token_hash = hash_token(&token);
+    let sql = db::returning_id(
+        "INSERT INTO axum_api_tokens (user_id, name, token_prefix, token_hash, scopes, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+    );
+    let id = observe_query(
+        "create_token",
+        sqlx::query(&sql)
+            .bind(user_id)
+            .bind(name)
+            .bind(display_prefix(&token))
+            .bind(&token_hash)
+            .bind(scopes.join(","))
+            .bind(created_at),
+        |q| db::fetch_insert_id(db_pool, q),
+    )
+    .await?;
+
+    Ok(CreatedApiToken {
+        id,
+        user_id,
+        name: name.to_string(),
+        scopes: scopes.to_vec(),
+        token,
+        created_at,
+    })
+}
@@ -0,0 +1,308 @@
+// OAuth2 / OpenID Connect 로그인 (authorization code + PKCE)
+// https://openid.net/specs/openid-connect-core-1_0.html
+// https://datatracker.ietf.org/doc/html/rfc7636 (PKCE)
+//
+// 흐름:
+//  1] GET /auth/oidc/login    -> state, nonce, code_verifier 생성 후 IdP authorization_endpoint로 redirect
+//  2] GET /auth/oidc/callback -> code를 token_endpoint에서 교환, ID 토큰 검증(JWKS, iss, aud, nonce),
+//                                sub/email을 axum_users 행에 매핑
+//
+// 처음 로그인한 sub는 새 사용자로 만듭니다. 같은 email의 기존 사용자에 연결하는 것은
+// OIDC_LINK_VERIFIED_EMAIL=true 이고 IdP가 email_verified=true 로 확인해 준 경우뿐입니다.
+// (확인되지 않은 email로 연결하면 그 email을 IdP에 등록한 누구나 기존 계정을 가져갈 수 있습니다.)
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{OnceCell, RwLock};
+
+// 로그인 시작 후 callback까지 허용하는 시간
+const PENDING_LOGIN_TTL: Duration = Duration::from_secs(600);
+
+#[derive(Clone, Debug)]
+pub struct OidcConfig {
+    /// 예: https://accounts.example.com/.well-known/openid-configuration
+    pub discovery_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// IdP에 등록된 callback 주소. 예: http://localhost:3000/auth/oidc/callback
+    pub redirect_url: String,
+    pub scopes: Vec<String>,
+    /// 로그인 성공 시 발급하는 개인 액세스 토큰의 스코프
+    pub token_scopes: Vec<String>,
+    /// IdP가 확인한(email_verified) email이 같은 기존 사용자에 연결할지 여부
+    pub link_verified_email: bool,
+    /// callback을 기다리는 로그인 수. 인증 없이 /auth/oidc/login 을 불러 메모리를 채우지 못하게 합니다.
+    pub max_pending_logins: usize,
+}
+
+impl OidcConfig {
+    /// `OIDC_DISCOVERY_URL`이 설정되어 있지 않으면 OIDC 로그인을 사용하지 않습니다.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let Ok(discovery_url) = env::var("OIDC_DISCOVERY_URL") else {
+            return Ok(None);
+        };
+        let required = |name: &str| env::var(name).map_err(|_| format!("{} must be set when OIDC_DISCOVERY_URL is set", name));
+        Ok(Some(OidcConfig {
+            discovery_url,
+            client_id: required("OIDC_CLIENT_ID")?,
+            client_secret: env::var("OIDC_CLIENT_SECRET").unwrap_or_default(),
+            redirect_url: required("OIDC_REDIRECT_URL")?,
+            scopes: env::var("OIDC_SCOPES")
+                .unwrap_or_else(|_| "openid email profile".to_string())
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            token_scopes: super::parse_scopes(
+                &env::var("OIDC_TOKEN_SCOPES").unwrap_or_else(|_| "users:read,tokens".to_string()),
+            ),
+            link_verified_email: env::var("OIDC_LINK_VERIFIED_EMAIL").map(|v| v == "true").unwrap_or(false),
+            max_pending_logins: env::var("OIDC_MAX_PENDING_LOGINS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10_000),
+        }))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OidcError {
+    #[error("identity provider request failed: {0}")]
+    Provider(String),
+    #[error("invalid login state")]
+    InvalidState,
+    #[error("too many logins in progress, try again later")]
+    TooManyPendingLogins,
+    #[error("invalid id token: {0}")]
+    InvalidIdToken(String),
+}
+
+/// discovery 문서 중 필요한 항목만
+#[derive(Deserialize, Clone, Debug)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IdTokenClaims {
+    pub sub: String,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub nonce: Option<String>,
+    /// bool이 표준이지만 "true" 문자열을 보내는 IdP도 있어 값 그대로 받습니다. `email_verified()`로 확인하세요.
+    #[serde(default)]
+    pub email_verified: Option<serde_json::Value>,
+}
+
+impl IdTokenClaims {
+    pub fn email_verified(&self) -> bool {
+        match &self.email_verified {
+            Some(serde_json::Value::Bool(verified)) => *verified,
+            Some(serde_json::Value::String(verified)) => verified == "true",
+            _ => false,
+        }
+    }
+}
+
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+    created: Instant,
+}
+
+pub struct OidcClient {
+    pub config: OidcConfig,
+    http: reqwest::Client,
+    metadata: OnceCell<ProviderMetadata>,
+    jwks: RwLock<Option<JwkSet>>,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+fn random_urlsafe(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand::rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// PKCE S256: BASE64URL(SHA256(code_verifier))
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+impl OidcClient {
+    pub fn new(config: OidcConfig) -> Self {
+        OidcClient {
+            config,
+            http: reqwest::Client::new(),
+            metadata: OnceCell::new(),
+            jwks: RwLock::new(None),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn metadata(&self) -> Result<&ProviderMetadata, OidcError> {
+        self.metadata
+            .get_or_try_init(|| async {
+                self.http
+                    .get(&self.config.discovery_url)
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| OidcError::Provider(e.to_string()))?
+                    .json::<ProviderMetadata>()
+                    .await
+                    .map_err(|e| OidcError::Provider(e.to_string()))
+            })
+            .await
+    }
+
+    /// 로그인 시작: PKCE/state/nonce를 만들고 IdP로 보낼 authorization URL을 반환합니다.
+    pub async fn authorization_url(&self) -> Result<String, OidcError> {
+        let metadata = self.metadata().await?;
+
+        let state = random_urlsafe(24);
+        let nonce = random_urlsafe(24);
+        let code_verifier = random_urlsafe(48);
+        let code_challenge = pkce_challenge(&code_verifier);
+
+        {
+            let mut pending = self.pending.lock().unwrap();
+            pending.retain(|_, p| p.created.elapsed() < PENDING_LOGIN_TTL);
+            if pending.len() >= self.config.max_pending_logins {
+                return Err(OidcError::TooManyPendingLogins);
+            }
+            pending.insert(
+                state.clone(),
+                PendingLogin { nonce: nonce.clone(), code_verifier, created: Instant::now() },
+            );
+        }
+
+        let mut url = reqwest::Url::parse(&metadata.authorization_endpoint)
+            .map_err(|e| OidcError::Provider(e.to_string()))?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_url)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &code_challenge)
+            .append_pair("code_challenge_method", "S256");
+
+        Ok(url.to_string())
+    }
+
+    /// callback 처리: state 확인, code 교환, ID 토큰 검증 후 claims를 반환합니다.
+    pub async fn exchange_code(&self, code: &str, state: &str) -> Result<IdTokenClaims, OidcError> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(state)
+            .filter(|p| p.created.elapsed() < PENDING_LOGIN_TTL)
+            .ok_or(OidcError::InvalidState)?;
+
+        let metadata = self.metadata().await?;
+
+        let token_response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &self.config.redirect_url),
+                ("client_id", &self.config.client_id),
+                ("client_secret", &self.config.client_secret),
+                ("code_verifier", &pending.code_verifier),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| OidcError::Provider(e.to_string()))?
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| OidcError::Provider(e.to_string()))?;
+
+        let id_token = token_response
+            .id_token
+            .ok_or_else(|| OidcError::InvalidIdToken("token response has no id_token".to_string()))?;
+
+        let claims = self.verify_id_token(&id_token).await?;
+        if claims.nonce.as_deref() != Some(pending.nonce.as_str()) {
+            return Err(OidcError::InvalidIdToken("nonce mismatch".to_string()));
+        }
+
+        Ok(claims)
+    }
+
+    async fn verify_id_token(&self, id_token: &str) -> Result<IdTokenClaims, OidcError> {
+        let metadata = self.metadata().await?;
+        let header = jsonwebtoken::decode_header(id_token)
+            .map_err(|e| OidcError::InvalidIdToken(e.to_string()))?;
+
+        // HS* 알고리즘은 client_secret을 키로 쓰므로 허용하지 않습니다.
+        if !matches!(
+            header.alg,
+            Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 | Algorithm::PS256
+                | Algorithm::PS384 | Algorithm::PS512 | Algorithm::ES256 | Algorithm::ES384
+                | Algorithm::EdDSA
+        ) {
+            return Err(OidcError::InvalidIdToken(format!("unsupported alg {:?}", header.alg)));
+        }
+
+        let key = self.decoding_key(&metadata.jwks_uri, header.kid.as_deref()).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_issuer(&[&metadata.issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| OidcError::InvalidIdToken(e.to_string()))
+    }
+
+    /// JWKS에서 kid에 맞는 키를 찾습니다. 키가 없으면 키 교체(rotation)로 보고 한 번 다시 받아옵니다.
+    async fn decoding_key(&self, jwks_uri: &str, kid: Option<&str>) -> Result<DecodingKey, OidcError> {
+        for refresh in [false, true] {
+            if refresh || self.jwks.read().await.is_none() {
+                let jwks = self
+                    .http
+                    .get(jwks_uri)
+                    .send()
+                    .await
+                    .and_then(|r| r.error_for_status())
+                    .map_err(|e| OidcError::Provider(e.to_string()))?
+                    .json::<JwkSet>()
+                    .await
+                    .map_err(|e| OidcError::Provider(e.to_string()))?;
+                *self.jwks.write().await = Some(jwks);
+            }
+
+            let jwks = self.jwks.read().await;
+            let jwk = match (jwks.as_ref(), kid) {
+                (Some(set), Some(kid)) => set.find(kid),
+                (Some(set), None) if set.keys.len() == 1 => set.keys.first(),
+                _ => None,
+            };
+            if let Some(jwk) = jwk {
+                return DecodingKey::from_jwk(jwk).map_err(|e| OidcError::InvalidIdToken(e.to_string()));
+            }
+        }
+
+        Err(OidcError::InvalidIdToken("no matching key in JWKS".to_string()))
+    }
+}
@@ -0,0 +1,483 @@
+// 부하 테스트 (예전 test_load_script.sh 대체)
+//
+// 그 스크립트와 같은 엔드포인트를 여러 worker가 동시에 호출하고,
+// 처리량, 오류 수, 시나리오별 p50/p95/p99 지연 시간을 텍스트(또는 JSON)로 출력합니다.
+//
+// 사용 예:
+//   cargo run --release --bin loadtest -- --url http://localhost:3000 --concurrency 50 --duration 30
+//   cargo run --release --bin loadtest -- --rate 200 --mix show-item=5,axum-users=1 --json report.json
+//
+// 관리자 키가 필요한 app-state 시나리오는 --admin-key 또는 ADMIN_API_KEY(.env)를 사용합니다.
+use rand::Rng;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+const USAGE: &str = "\
+Usage: loadtest [OPTIONS]
+
+Options:
+  --url <URL>            target base URL (default: http://localhost:3000)
+  --concurrency <N>      concurrent workers (default: 10)
+  --duration <SECS>      test duration in seconds (default: 10)
+  --rate <N>             total requests per second up to 1000000, 0 = as fast as possible (default: 0)
+  --requests <N>         stop after N requests (default: unlimited)
+  --mix <LIST>           scenario weights, e.g. show-item=5,axum-users=1 (default: all scenarios, weight 1)
+  --admin-key <KEY>      X-Admin-API-Key for app-state (default: $ADMIN_API_KEY)
+  --timeout <SECS>       per-request timeout (default: 10)
+  --json <FILE>          also write the report as JSON, '-' prints only JSON to stdout
+  -h, --help             print this help
+
+Scenarios: create-user, list-users, show-item, add-item, delete-user, axum-users, app-state";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Scenario {
+    CreateUser,
+    ListUsers,
+    ShowItem,
+    AddItem,
+    DeleteUser,
+    AxumUsers,
+    AppState,
+}
+
+impl Scenario {
+    const ALL: [Scenario; 7] = [
+        Scenario::CreateUser,
+        Scenario::ListUsers,
+        Scenario::ShowItem,
+        Scenario::AddItem,
+        Scenario::DeleteUser,
+        Scenario::AxumUsers,
+        Scenario::AppState,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Scenario::CreateUser => "create-user",
+            Scenario::ListUsers => "list-users",
+            Scenario::ShowItem => "show-item",
+            Scenario::AddItem => "add-item",
+            Scenario::DeleteUser => "delete-user",
+            Scenario::AxumUsers => "axum-users",
+            Scenario::AppState => "app-state",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Scenario> {
+        Scenario::ALL.into_iter().find(|s| s.name() == name)
+    }
+
+    fn request(self, client: &reqwest::Client, base: &str, admin_key: &str) -> reqwest::RequestBuilder {
+        match self {
+            Scenario::CreateUser => client.post(format!("{}/create-user", base)),
+            Scenario::ListUsers => client.get(format!("{}/users", base)),
+            Scenario::ShowItem => client.get(format!("{}/item/42?number=2", base)),
+            Scenario::AddItem => client
+                .post(format!("{}/add-item", base))
+                .json(&serde_json::json!({ "title": "Some random item" })),
+            Scenario::DeleteUser => client.delete(format!("{}/delete-user/2", base)),
+            Scenario::AxumUsers => client.get(format!("{}/axum-users", base)),
+            Scenario::AppState => client
+                .get(format!("{}/admin/get_app_state", base))
+                .header("X-Admin-API-Key", admin_key),
+        }
+    }
+}
+
+/// --rate 상한. ticker 간격(1/rate초)이 0이 되지 않고 허가 수가 usize에 들어가는 범위
+const MAX_RATE: u32 = 1_000_000;
+
+const FLAGS: &[&str] = &[
+    "--url", "--concurrency", "--duration", "--rate", "--requests", "--mix", "--admin-key", "--timeout", "--json",
+];
+
+struct Options {
+    url: String,
+    concurrency: usize,
+    duration: Duration,
+    rate: u32,
+    requests: Option<u64>,
+    mix: Vec<(Scenario, u32)>,
+    admin_key: String,
+    timeout: Duration,
+    json: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        url: "http://localhost:3000".to_string(),
+        concurrency: 10,
+        duration: Duration::from_secs(10),
+        rate: 0,
+        requests: None,
+        mix: Scenario::ALL.into_iter().map(|s| (s, 1)).collect(),
+        admin_key: env::var("ADMIN_API_KEY").unwrap_or_default(),
+        timeout: Duration::from_secs(10),
+        json: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        if flag == "-h" || flag == "--help" {
+            return Err(String::new());
+        }
+        if !FLAGS.contains(&flag.as_str()) {
+            return Err(format!("unknown option '{}'", flag));
+        }
+        let value = match inline.or_else(|| args.next()) {
+            Some(value) => value,
+            None => return Err(format!("{} requires a value", flag)),
+        };
+        let number = |v: &str| v.parse::<u64>().map_err(|_| format!("{} must be a number, got '{}'", flag, v));
+
+        match flag.as_str() {
+            "--url" => options.url = value.trim_end_matches('/').to_string(),
+            "--concurrency" => options.concurrency = number(&value)?.max(1) as usize,
+            "--duration" => options.duration = Duration::from_secs(number(&value)?),
+            "--rate" => {
+                options.rate = u32::try_from(number(&value)?)
+                    .ok()
+                    .filter(|rate| *rate <= MAX_RATE)
+                    .ok_or_else(|| format!("--rate must be between 0 and {}, got '{}'", MAX_RATE, value))?;
+            }
+            "--requests" => options.requests = Some(number(&value)?),
+            "--admin-key" => options.admin_key = value,
+            "--timeout" => options.timeout = Duration::from_secs(number(&value)?),
+            "--json" => options.json = Some(value),
+            "--mix" => {
+                let mut mix = Vec::new();
+                for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                    let (name, weight) = entry.split_once('=').unwrap_or((entry, "1"));
+                    let scenario = Scenario::parse(name.trim()).ok_or_else(|| format!("unknown scenario '{}'", name))?;
+                    let weight = weight.trim().parse::<u32>().map_err(|_| format!("invalid weight in '{}'", entry))?;
+                    if weight > 0 {
+                        mix.push((scenario, weight));
+                    }
+                }
+                if mix.is_empty() {
+                    return Err("--mix needs at least one scenario with a weight above 0".to_string());
+                }
+                options.mix = mix;
+            }
+            _ => unreachable!(),
+        }
+    }
+    Ok(options)
+}
+
+fn pick(mix: &[(Scenario, u32)], total_weight: u32) -> Scenario {
+    let mut n = rand::rng().random_range(0..total_weight);
+    for &(scenario, weight) in mix {
+        if n < weight {
+            return scenario;
+        }
+        n -= weight;
+    }
+    mix[mix.len() - 1].0
+}
+
+/// worker 하나가 모은 결과. 끝나면 시나리오별로 합칩니다.
+#[derive(Default)]
+struct Samples {
+    latencies: Vec<Duration>,
+    /// 상태 코드별 응답 수. 0은 연결 실패, 타임아웃 등 전송 오류
+    statuses: BTreeMap<u16, u64>,
+}
+
+#[derive(Serialize)]
+struct ScenarioReport {
+    requests: u64,
+    errors: u64,
+    statuses: BTreeMap<String, u64>,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+}
+
+#[derive(Serialize)]
+struct Report {
+    url: String,
+    concurrency: usize,
+    rate: u32,
+    elapsed_secs: f64,
+    requests: u64,
+    errors: u64,
+    throughput_rps: f64,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+    scenarios: BTreeMap<&'static str, ScenarioReport>,
+}
+
+/// nearest-rank 백분위수 (`sorted`는 오름차순)
+fn percentile(sorted: &[Duration], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1].as_secs_f64() * 1000.0
+}
+
+fn is_error(status: u16) -> bool {
+    !(200..400).contains(&status)
+}
+
+fn build_report(options: &Options, elapsed: Duration, results: BTreeMap<Scenario, Samples>) -> Report {
+    let mut all = Vec::new();
+    let mut scenarios = BTreeMap::new();
+    let (mut requests, mut errors) = (0, 0);
+
+    for (scenario, mut samples) in results {
+        samples.latencies.sort();
+        let count = samples.latencies.len() as u64;
+        let failed: u64 = samples.statuses.iter().filter(|(s, _)| is_error(**s)).map(|(_, n)| n).sum();
+        requests += count;
+        errors += failed;
+        scenarios.insert(
+            scenario.name(),
+            ScenarioReport {
+                requests: count,
+                errors: failed,
+                statuses: samples
+                    .statuses
+                    .iter()
+                    .map(|(s, n)| (if *s == 0 { "transport_error".to_string() } else { s.to_string() }, *n))
+                    .collect(),
+                p50_ms: percentile(&samples.latencies, 50.0),
+                p95_ms: percentile(&samples.latencies, 95.0),
+                p99_ms: percentile(&samples.latencies, 99.0),
+                max_ms: samples.latencies.last().map(|d| d.as_secs_f64() * 1000.0).unwrap_or(0.0),
+            },
+        );
+        all.extend(samples.latencies);
+    }
+    all.sort();
+
+    Report {
+        url: options.url.clone(),
+        concurrency: options.concurrency,
+        rate: options.rate,
+        elapsed_secs: elapsed.as_secs_f64(),
+        requests,
+        errors,
+        throughput_rps: requests as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        p50_ms: percentile(&all, 50.0),
+        p95_ms: percentile(&all, 95.0),
+        p99_ms: percentile(&all, 99.0),
+        scenarios,
+    }
+}
+
+fn print_text(report: &Report) {
+    println!("target:      {}", report.url);
+    println!(
+        "workers:     {} (rate: {})",
+        report.concurrency,
+        if report.rate == 0 { "unlimited".to_string() } else { format!("{}/s", report.rate) }
+    );
+    println!("elapsed:     {:.2}s", report.elapsed_secs);
+    println!("requests:    {} ({} errors)", report.requests, report.errors);
+    println!("throughput:  {:.1} req/s", report.throughput_rps);
+    println!(
+        "latency:     p50 {:.2}ms  p95 {:.2}ms  p99 {:.2}ms",
+        report.p50_ms, report.p95_ms, report.p99_ms
+    );
+    println!();
+    println!(
+        "{:<12} {:>9} {:>7} {:>9} {:>9} {:>9} {:>9}  statuses",
+        "scenario", "requests", "errors", "p50 ms", "p95 ms", "p99 ms", "max ms"
+    );
+    for (name, s) in &report.scenarios {
+        let statuses: Vec<String> = s.statuses.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        println!(
+            "{:<12} {:>9} {:>7} {:>9.2} {:>9.2} {:>9.2} {:>9.2}  {}",
+            name,
+            s.requests,
+            s.errors,
+            s.p50_ms,
+            s.p95_ms,
+            s.p99_ms,
+            s.max_ms,
+            statuses.join(" ")
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => Arc::new(options),
+        Err(message) => {
+            if message.is_empty() {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(options.timeout)
+        .pool_max_idle_per_host(options.concurrency)
+        .build()
+        .expect("failed to build HTTP client");
+    let total_weight: u32 = options.mix.iter().map(|(_, w)| w).sum();
+
+    // --rate가 있으면 ticker가 초당 rate개의 허가를 내주고, worker는 허가를 받아야 요청합니다.
+    let permits = (options.rate > 0).then(|| Arc::new(Semaphore::new(0)));
+    let start = Instant::now();
+    let deadline = start + options.duration;
+    let issued = Arc::new(std::sync::atomic::AtomicU64::new(0));
+
+    let ticker = permits.clone().map(|permits| {
+        let rate = options.rate;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / rate as f64));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Burst);
+            loop {
+                interval.tick().await;
+                // 밀린 허가가 쌓여 한꺼번에 몰리지 않도록 1초 분량까지만 보관합니다.
+                if permits.available_permits() < rate.max(1) as usize {
+                    permits.add_permits(1);
+                }
+            }
+        })
+    });
+
+    let mut workers = tokio::task::JoinSet::new();
+    for _ in 0..options.concurrency {
+        let client = client.clone();
+        let options = options.clone();
+        let permits = permits.clone();
+        let issued = issued.clone();
+        workers.spawn(async move {
+            let mut results: BTreeMap<Scenario, Samples> = BTreeMap::new();
+            loop {
+                if let Some(permits) = &permits {
+                    match tokio::time::timeout_at(deadline.into(), permits.acquire()).await {
+                        Ok(Ok(permit)) => permit.forget(),
+                        _ => break,
+                    }
+                }
+                if Instant::now() >= deadline {
+                    break;
+                }
+                let n = issued.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                if options.requests.is_some_and(|max| n >= max) {
+                    break;
+                }
+
+                let scenario = pick(&options.mix, total_weight);
+                let sent = Instant::now();
+                let status = match scenario.request(&client, &options.url, &options.admin_key).send().await {
+                    // 본문까지 받아야 지연 시간이 의미가 있습니다.
+                    Ok(res) => {
+                        let status = res.status().as_u16();
+                        match res.bytes().await {
+                            Ok(_) => status,
+                            Err(_) => 0,
+                        }
+                    }
+                    Err(_) => 0,
+                };
+                let samples = results.entry(scenario).or_default();
+                samples.latencies.push(sent.elapsed());
+                *samples.statuses.entry(status).or_default() += 1;
+            }
+            results
+        });
+    }
+
+    let mut merged: BTreeMap<Scenario, Samples> = BTreeMap::new();
+    while let Some(result) = workers.join_next().await {
+        for (scenario, samples) in result.expect("worker panicked") {
+            let entry = merged.entry(scenario).or_default();
+            entry.latencies.extend(samples.latencies);
+            for (status, n) in samples.statuses {
+                *entry.statuses.entry(status).or_default() += n;
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    if let Some(ticker) = ticker {
+        ticker.abort();
+    }
+
+    let report = build_report(&options, elapsed, merged);
+    match options.json.as_deref() {
+        Some("-") => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+        Some(path) => {
+            print_text(&report);
+            if let Err(e) = std::fs::write(path, serde_json::to_string_pretty(&report).unwrap()) {
+                eprintln!("error: failed to write {}: {}", path, e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print_text(&report),
+    }
+
+    if report.errors > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_flags_and_inline_values() {
+        let options = parse(&["--url", "http://example.com:8080/", "--concurrency=0", "--rate", "200", "--requests=5"]).unwrap();
+        assert_eq!(options.url, "http://example.com:8080");
+        assert_eq!(options.concurrency, 1);
+        assert_eq!(options.rate, 200);
+        assert_eq!(options.requests, Some(5));
+        assert_eq!(options.mix.len(), Scenario::ALL.len());
+
+        let options = parse(&["--mix", "show-item=5, axum-users ,delete-user=0", "--duration", "3"]).unwrap();
+        assert_eq!(options.mix, [(Scenario::ShowItem, 5), (Scenario::AxumUsers, 1)]);
+        assert_eq!(options.duration, Duration::from_secs(3));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        assert_eq!(parse(&["--help"]).err().as_deref(), Some(""));
+        assert!(parse(&["--bogus", "1"]).err().unwrap().contains("unknown option"));
+        assert!(parse(&["--rate"]).err().unwrap().contains("requires a value"));
+        assert!(parse(&["--duration", "ten"]).err().unwrap().contains("must be a number"));
+        assert!(parse(&["--mix", "nope=1"]).err().unwrap().contains("unknown scenario"));
+        assert!(parse(&["--mix", "show-item=0"]).is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_rates() {
+        assert_eq!(parse(&["--rate", "1000000"]).unwrap().rate, MAX_RATE);
+        assert!(parse(&["--rate", "1000001"]).err().unwrap().contains("--rate must be between"));
+        // u32로 자르면 0(무제한)이 되던 값
+        assert!(parse(&["--rate=4294967296"]).is_err());
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        assert_eq!(percentile(&[], 50.0), 0.0);
+        let sorted: Vec<Duration> = (1..=10).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 5.0);
+        assert_eq!(percentile(&sorted, 95.0), 10.0);
+        assert_eq!(percentile(&sorted, 100.0), 10.0);
+        assert_eq!(percentile(&sorted[..1], 99.0), 1.0);
+    }
+}
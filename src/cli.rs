@@ -0,0 +1,241 @@
+// 관리용 CLI 명령
+//
+// 서버 바이너리의 첫 번째 인자로 명령을 고릅니다. 인자가 없으면 `serve`입니다.
+// `serve` 외의 명령은 로그를 켜지 않고 결과만 stdout에 출력하므로 스크립트에서 쓰기 좋습니다.
+//
+// 사용 예:
+//   axum-rest-api migrate
+//   axum-rest-api user add "zorba house" zorba@example.com
+//   axum-rest-api user list --json
+//   axum-rest-api check-config
+//   axum-rest-api openapi export --output openapi.json
+//   axum-rest-api openapi export legacy --output openapi-legacy.json
+use rand::RngCore;
+use serde_json::json;
+use sqlx::Row;
+use std::env;
+use std::path::PathBuf;
+use utoipa::OpenApi;
+use crate::db::{self, DbPool};
+use crate::handlers::v1::insert_user;
+use crate::{connect_db, load_config, openapi, tls, AppConfig};
+
+pub const USAGE: &str = "\
+Usage: axum-rest-api [COMMAND]
+
+Commands:
+  serve                              run the HTTP server (default)
+  migrate                            apply database migrations (MIGRATIONS_DIR, default: ./migrations)
+  create-admin-key                   print a new random value for ADMIN_API_KEY
+  user add <NAME> <EMAIL>            insert a user into axum_users
+  user list [--json]                 list users
+  user delete <ID>                   delete a user and its API tokens
+  check-config                       validate the environment, database and TLS files
+  openapi export [v1|legacy] [--output <FILE>]
+                                     write the OpenAPI document as JSON (default: v1, stdout)
+  help                               print this help";
+
+/// `openapi export`로 내보낼 문서
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ApiVersion {
+    /// /api/v1 (`openapi::ApiDocV1`)
+    V1,
+    /// 버전 없는 예전 경로 (`openapi::ApiDoc`)
+    Legacy,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Serve,
+    Migrate,
+    CreateAdminKey,
+    UserAdd { name: String, email: String },
+    UserList { json: bool },
+    UserDelete { id: i32 },
+    CheckConfig,
+    OpenApiExport { version: ApiVersion, output: Option<PathBuf> },
+    Help,
+}
+
+impl Command {
+    /// 프로그램 이름을 뺀 인자 목록을 해석합니다.
+    pub fn parse(args: &[String]) -> Result<Command, String> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let command = match args.as_slice() {
+            [] | ["serve"] => Command::Serve,
+            ["migrate"] => Command::Migrate,
+            ["create-admin-key"] => Command::CreateAdminKey,
+            ["user", "add", name, email] => Command::UserAdd { name: name.to_string(), email: email.to_string() },
+            ["user", "list"] => Command::UserList { json: false },
+            ["user", "list", "--json"] => Command::UserList { json: true },
+            ["user", "delete", id] => Command::UserDelete {
+                id: id.parse().map_err(|_| format!("user id must be a number, got '{}'", id))?,
+            },
+            ["check-config"] => Command::CheckConfig,
+            ["openapi", "export", rest @ ..] => {
+                let (version, rest) = match rest {
+                    ["v1", rest @ ..] => (ApiVersion::V1, rest),
+                    ["legacy", rest @ ..] => (ApiVersion::Legacy, rest),
+                    rest => (ApiVersion::V1, rest),
+                };
+                let output = match rest {
+                    [] => None,
+                    ["--output" | "-o", path] => Some(PathBuf::from(path)),
+                    _ => return Err(format!("unrecognized arguments: {}", args.join(" "))),
+                };
+                Command::OpenApiExport { version, output }
+            }
+            ["help" | "--help" | "-h"] => Command::Help,
+            _ => return Err(format!("unrecognized arguments: {}", args.join(" "))),
+        };
+        Ok(command)
+    }
+}
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// `serve`를 뺀 명령을 실행합니다.
+pub async fn run(command: Command) -> CliResult {
+    match command {
+        Command::Serve => unreachable!("serve is handled by main"),
+        Command::Help => println!("{}", USAGE),
+        Command::CreateAdminKey => create_admin_key(),
+        Command::OpenApiExport { version, output } => openapi_export(version, output)?,
+        // DB만 쓰는 명령은 ADMIN_API_KEY, TLS 같은 서버 설정 없이 연결만 합니다.
+        Command::Migrate => migrate(&connect_db().await?).await?,
+        Command::UserAdd { name, email } => user_add(&connect_db().await?, &name, &email).await?,
+        Command::UserList { json } => user_list(&connect_db().await?, json).await?,
+        Command::UserDelete { id } => user_delete(&connect_db().await?, id).await?,
+        Command::CheckConfig => check_config().await?,
+    }
+    Ok(())
+}
+
+fn migrations_dir() -> PathBuf {
+    PathBuf::from(env::var("MIGRATIONS_DIR").unwrap_or_else(|_| "migrations".to_string()))
+}
+
+async fn migrate(db_pool: &DbPool) -> CliResult {
+    let dir = migrations_dir();
+    db::run_migrations(db_pool, &dir).await?;
+    println!("migrations applied from {} ({})", dir.display(), db::system());
+    Ok(())
+}
+
+fn create_admin_key() {
+    let mut bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut bytes);
+    println!("ADMIN_API_KEY={}", hex::encode(bytes));
+}
+
+fn openapi_export(version: ApiVersion, output: Option<PathBuf>) -> CliResult {
+    let spec = match version {
+        ApiVersion::V1 => openapi::ApiDocV1::openapi(),
+        ApiVersion::Legacy => openapi::ApiDoc::openapi(),
+    }
+    .to_pretty_json()?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, spec + "\n")?;
+            eprintln!("OpenAPI document written to {}", path.display());
+        }
+        None => println!("{}", spec),
+    }
+    Ok(())
+}
+
+async fn user_add(db_pool: &DbPool, name: &str, email: &str) -> CliResult {
+    let id = insert_user(db_pool, name, email).await?;
+    println!("created user {} ({} <{}>)", id, name, email);
+    Ok(())
+}
+
+async fn user_list(db_pool: &DbPool, as_json: bool) -> CliResult {
+    let rows = sqlx::query("SELECT id, name, email FROM axum_users ORDER BY id")
+        .fetch_all(db_pool)
+        .await?;
+    let users = rows
+        .iter()
+        .map(|row| Ok((row.try_get::<i32, _>("id")?, row.try_get::<String, _>("name")?, row.try_get::<String, _>("email")?)))
+        .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+    if as_json {
+        let users: Vec<_> = users
+            .iter()
+            .map(|(id, name, email)| json!({ "id": id, "name": name, "email": email }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&users)?);
+    } else {
+        println!("{:>6}  {:<30}  email", "id", "name");
+        for (id, name, email) in users {
+            println!("{:>6}  {:<30}  {}", id, name, email);
+        }
+    }
+    Ok(())
+}
+
+async fn user_delete(db_pool: &DbPool, id: i32) -> CliResult {
+    // 토큰은 FOREIGN KEY ... ON DELETE CASCADE로 같이 지워집니다.
+    let result = sqlx::query("DELETE FROM axum_users WHERE id = ?").bind(id).execute(db_pool).await?;
+    if result.rows_affected() == 0 {
+        return Err(format!("user {} not found", id).into());
+    }
+    println!("deleted user {}", id);
+    Ok(())
+}
+
+/// 설정을 읽고 DB 연결, 마이그레이션 디렉터리, TLS 파일을 확인합니다. 하나라도 실패하면 오류로 끝납니다.
+async fn check_config() -> CliResult {
+    // 환경 변수를 읽지 못하거나 DB에 연결하지 못하면 나머지는 확인할 수 없습니다.
+    let config: AppConfig = match load_config().await {
+        Ok(config) => config,
+        Err(e) => {
+            println!("FAIL  {:<12} {}", "config", e);
+            return Err("1 check(s) failed".into());
+        }
+    };
+    let mut failures = 0;
+    let mut report = |name: &str, result: Result<String, String>| match result {
+        Ok(detail) => println!("ok    {:<12} {}", name, detail),
+        Err(detail) => {
+            failures += 1;
+            println!("FAIL  {:<12} {}", name, detail);
+        }
+    };
+
+    let ping = sqlx::query("SELECT 1").execute(&config.db_pool).await;
+    report("database", ping.map(|_| db::system().to_string()).map_err(|e| e.to_string()));
+
+    let dir = migrations_dir();
+    report(
+        "migrations",
+        if dir.is_dir() { Ok(dir.display().to_string()) } else { Err(format!("{} is not a directory", dir.display())) },
+    );
+
+    report("listen", Ok(format!("{}:{}", config.host, config.port)));
+
+    let tls = match &config.tls {
+        Some(tls_config) => tls::ReloadableCert::load(tls_config.clone())
+            .map(|_| format!("{} (client CA: {})", tls_config.cert_path.display(), tls_config.client_ca_path.is_some()))
+            .map_err(|e| e.to_string()),
+        None => Ok("disabled".to_string()),
+    };
+    report("tls", tls);
+
+    let oidc = match &config.app_state.oidc {
+        Some(_) => "enabled",
+        None => "disabled",
+    };
+    report("oidc", Ok(oidc.to_string()));
+    report("admin key", match config.app_state.admin_api_key.len() {
+        0 => Err("ADMIN_API_KEY is empty".to_string()),
+        n if n < 16 => Err(format!("ADMIN_API_KEY is only {} characters, use create-admin-key", n)),
+        n => Ok(format!("{} characters", n)),
+    });
+
+    config.db_pool.close().await;
+    if failures > 0 {
+        return Err(format!("{} check(s) failed", failures).into());
+    }
+    Ok(())
+}
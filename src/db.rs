@@ -0,0 +1,87 @@
+// DB 연결
+//
+// sqlx `Any` 드라이버를 사용하므로 같은 코드로 MySQL(운영)과 SQLite(로컬 개발, 통합 테스트)를 모두 지원합니다.
+// 두 DB 모두 `?` 자리표시자를 쓰므로 쿼리 문자열은 그대로입니다.
+// 시각은 BIGINT(unix epoch 초)로 저장해 DB마다 다른 날짜 타입을 피합니다.
+//
+// 스키마는 DB별로 migrations/mysql, migrations/sqlite 에 있습니다.
+// 새 행의 id는 `returning_id` + `fetch_insert_id`로 받습니다 (MySQL은 last_insert_id, SQLite는 RETURNING).
+//
+// 환경 변수:
+//   DATABASE_URL=sqlite://./dev.db?mode=rwc   (설정하면 DB_* 대신 사용)
+use sqlx::any::{install_default_drivers, AnyArguments, AnyPoolOptions, AnyRow};
+use sqlx::query::Query;
+use sqlx::{Any, Row, TypeInfo, ValueRef};
+use sqlx::migrate::{MigrateError, Migrator};
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub type DbPool = sqlx::AnyPool;
+
+static DB_SYSTEM: OnceLock<&'static str> = OnceLock::new();
+
+fn system_for_scheme(scheme: &str) -> &'static str {
+    match scheme {
+        "sqlite" => "sqlite",
+        "mysql" | "mariadb" => "mysql",
+        _ => "other_sql",
+    }
+}
+
+/// URL의 scheme에 맞는 드라이버로 연결합니다 (mysql://, sqlite:).
+pub async fn connect(url: &str) -> Result<DbPool, sqlx::Error> {
+    install_default_drivers();
+    let scheme = url.split(':').next().unwrap_or_default();
+    let _ = DB_SYSTEM.set(system_for_scheme(scheme));
+
+    AnyPoolOptions::new().connect(url).await
+}
+
+/// trace의 `db.system` 값 (OpenTelemetry semantic conventions)
+pub fn system() -> &'static str {
+    DB_SYSTEM.get().copied().unwrap_or("other_sql")
+}
+
+/// migrations/<db>/ 의 마이그레이션을 적용합니다. `root`는 migrations 디렉터리입니다.
+pub async fn run_migrations(db_pool: &DbPool, root: &Path) -> Result<(), MigrateError> {
+    let dir = match system_for_scheme(db_pool.connect_options().database_url.scheme()) {
+        "sqlite" => root.join("sqlite"),
+        _ => root.join("mysql"),
+    };
+    Migrator::new(dir).await?.run(db_pool).await
+}
+
+/// NULL일 수 있는 컬럼을 읽습니다. sqlx 0.7 `Any` 드라이버는 NULL 값을 `Option<T>`로 디코딩하지 못하고
+/// `is_null()`도 항상 false이므로, 값의 타입 이름(`NULL`)으로 확인합니다.
+pub fn get_nullable<'r, T>(row: &'r AnyRow, column: &str) -> Result<Option<T>, sqlx::Error>
+where
+    T: sqlx::Decode<'r, Any> + sqlx::Type<Any>,
+{
+    if row.try_get_raw(column)?.type_info().name() == "NULL" {
+        return Ok(None);
+    }
+    row.try_get(column).map(Some)
+}
+
+/// 새 행의 id를 돌려받도록 INSERT 문을 고칩니다. SQLite는 `RETURNING id`를 붙이고 MySQL은 그대로 둡니다.
+/// 실행은 `fetch_insert_id`로 합니다.
+pub fn returning_id(sql: &str) -> String {
+    match system() {
+        "sqlite" => format!("{} RETURNING id", sql),
+        _ => sql.to_string(),
+    }
+}
+
+/// `returning_id`로 만든 INSERT 문을 실행하고 새 행의 id를 돌려줍니다.
+/// sqlx 0.7 `Any` 드라이버는 SQLite에서 last_insert_id를 주지 않으므로 RETURNING 결과를 읽습니다.
+pub async fn fetch_insert_id<'q>(db_pool: &DbPool, query: Query<'q, Any, AnyArguments<'q>>) -> Result<i32, sqlx::Error> {
+    if system() == "sqlite" {
+        return query.fetch_one(db_pool).await?.try_get("id");
+    }
+    query
+        .execute(db_pool)
+        .await?
+        .last_insert_id()
+        .map(|id| id as i32)
+        .ok_or_else(|| sqlx::Error::Protocol("INSERT did not return last_insert_id".to_string()))
+}
@@ -0,0 +1,143 @@
+// 사용자 변경 알림 (GET /api/v1/users/events, Server-Sent Events)
+// 관리자 키 또는 `users:read` 스코프 토큰이 필요합니다 (/ws의 users 토픽과 같은 정책).
+//
+// 사용자를 만들고, 바꾸고, 지우는 핸들러가 `UserEvents::publish`를 부르면
+// 연결된 SSE 구독자에게 보내고, 최근 이벤트를 replay 버퍼에 남깁니다.
+// 다시 연결한 클라이언트는 Last-Event-ID 이후의 이벤트를 버퍼에서 받습니다.
+// 버퍼에 없는 구간을 요청하면(너무 오래됐거나 서버가 재시작됨) `resync` 이벤트를 먼저 보내므로,
+// 클라이언트는 목록을 다시 읽어야 합니다.
+// 이벤트 id는 서버 시작 시각(ms) * 1000 다음부터 1씩 늘어나므로, 재시작 전의 id로 연결해도 resync가 됩니다.
+//
+// 아이템 추가(`ItemEvents`)는 버퍼 없이 WebSocket(`ws`)으로만 알립니다.
+//
+// 이 프로세스 안의 변경만 알 수 있습니다. CLI(`user add`)나 다른 인스턴스의 변경은 보이지 않습니다.
+//
+// 환경 변수:
+//   USER_EVENTS_REPLAY=256     replay 버퍼 크기 (이벤트 수)
+//   SSE_KEEP_ALIVE_SECS=15     연결 유지용 주석(`: keep-alive`) 간격
+use std::collections::VecDeque;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+use crate::models::{BodyItem, ItemChange, ItemEvent, User, UserChange, UserEvent};
+
+#[derive(Clone, Debug)]
+pub struct UserEventsConfig {
+    pub replay: usize,
+    pub keep_alive: Duration,
+}
+
+impl UserEventsConfig {
+    pub fn from_env() -> Self {
+        let replay = env::var("USER_EVENTS_REPLAY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(256);
+        let keep_alive = env::var("SSE_KEEP_ALIVE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(15);
+        UserEventsConfig { replay, keep_alive: Duration::from_secs(keep_alive) }
+    }
+}
+
+struct Replay {
+    /// 마지막으로 발행한 이벤트 id
+    last_id: u64,
+    buffer: VecDeque<UserEvent>,
+}
+
+pub struct UserEvents {
+    pub config: UserEventsConfig,
+    tx: broadcast::Sender<UserEvent>,
+    replay: Mutex<Replay>,
+}
+
+fn first_id_base() -> u64 {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+    millis * 1000
+}
+
+/// 구독 시점까지 놓친 이벤트와 이후의 실시간 이벤트
+pub struct Subscription {
+    /// 요청한 id 다음부터 버퍼에 연속으로 남아 있지 않아 목록을 다시 읽어야 함. 값은 지금까지 발행한 마지막 id
+    pub resync: Option<u64>,
+    pub missed: Vec<UserEvent>,
+    pub live: broadcast::Receiver<UserEvent>,
+}
+
+impl UserEvents {
+    pub fn new(config: UserEventsConfig) -> Self {
+        // 느린 구독자가 이만큼 뒤처지면 연결을 끊고, 클라이언트는 Last-Event-ID로 다시 받습니다.
+        let (tx, _) = broadcast::channel(config.replay.max(16));
+        UserEvents {
+            config,
+            tx,
+            replay: Mutex::new(Replay { last_id: first_id_base(), buffer: VecDeque::new() }),
+        }
+    }
+
+    pub fn publish(&self, change: UserChange, user: User) -> u64 {
+        // 잠금 안에서 보내야 `subscribe`의 버퍼 복사와 실시간 이벤트가 겹치거나 빠지지 않습니다.
+        let mut replay = self.replay.lock().unwrap();
+        replay.last_id += 1;
+        let event = UserEvent { id: replay.last_id, change, user };
+        if self.config.replay > 0 {
+            if replay.buffer.len() == self.config.replay {
+                replay.buffer.pop_front();
+            }
+            replay.buffer.push_back(event.clone());
+        }
+        // 구독자가 없으면 Err이지만 무시합니다.
+        let _ = self.tx.send(event);
+        replay.last_id
+    }
+
+    /// `last_event_id`가 없으면 실시간 이벤트만 받습니다.
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> Subscription {
+        let replay = self.replay.lock().unwrap();
+        let live = self.tx.subscribe();
+        let Some(last_event_id) = last_event_id else {
+            return Subscription { resync: None, missed: Vec::new(), live };
+        };
+
+        let oldest = replay.buffer.front().map(|e| e.id).unwrap_or(replay.last_id + 1);
+        if last_event_id > replay.last_id || last_event_id + 1 < oldest {
+            // 목록을 다시 읽으면 버퍼의 이벤트도 반영되어 있으므로 보내지 않습니다.
+            return Subscription { resync: Some(replay.last_id), missed: Vec::new(), live };
+        }
+        let missed = replay.buffer.iter().filter(|e| e.id > last_event_id).cloned().collect();
+        Subscription { resync: None, missed, live }
+    }
+}
+
+/// 구독자(WebSocket 연결)마다 이만큼 쌓이면 Lagged. 연결은 이벤트를 바로 전송 대기열로 옮기므로 거의 차지 않습니다.
+const ITEM_EVENTS_CAPACITY: usize = 256;
+
+pub struct ItemEvents {
+    tx: broadcast::Sender<ItemEvent>,
+    last_id: Mutex<u64>,
+}
+
+impl Default for ItemEvents {
+    fn default() -> Self {
+        let (tx, _) = broadcast::channel(ITEM_EVENTS_CAPACITY);
+        ItemEvents { tx, last_id: Mutex::new(first_id_base()) }
+    }
+}
+
+impl ItemEvents {
+    pub fn publish(&self, change: ItemChange, item: BodyItem) -> u64 {
+        // id 순서와 보내는 순서가 같도록 잠금 안에서 보냅니다.
+        let mut last_id = self.last_id.lock().unwrap();
+        *last_id += 1;
+        let _ = self.tx.send(ItemEvent { id: *last_id, change, item });
+        *last_id
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ItemEvent> {
+        self.tx.subscribe()
+    }
+}
@@ -0,0 +1,36 @@
+use axum::{
+    extract::Query,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use crate::metrics::slow::SLOW_EVENTS;
+use crate::handlers::{AcceptFormat, Negotiated};
+
+#[derive(Deserialize)]
+pub struct SlowEventsParams {
+    pub limit: Option<usize>,
+}
+
+//-- 최근 느린 쿼리 / 요청 목록 ----------------
+#[utoipa::path(
+    get,
+    path = "/admin/slow-events",
+    params(
+        ("limit" = Option<usize>, Query, description = "Maximum number of events (default 50)")
+    ),
+    responses(
+        (status = 200, description = "Recent slow queries and requests, newest first", body = [SlowEvent]),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Admin scope required", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn list_slow_events(
+    AcceptFormat(format): AcceptFormat,
+    Query(params): Query<SlowEventsParams>,
+) -> impl IntoResponse {
+    Negotiated(format, SLOW_EVENTS.recent(params.limit.unwrap_or(50)))
+}
@@ -5,6 +5,8 @@ use axum::{
 };
 // use serde_json::json;
 use thiserror::Error;
+use crate::middleware::RequestId;
+use crate::models::ErrorResponse;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -12,6 +14,30 @@ pub enum AppError {
     UserNotFound(i32, String),
     #[error("Invalid Input: {0} - {1}")]
     InvalidInput(i32, String),
+    #[error("Token Not Found: {0} - {1}")]
+    TokenNotFound(i32, String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Not Found: {0}")]
+    NotFound(String),
+    #[error("Upstream error: {0}")]
+    UpstreamError(String),
+    #[error("Too Many Requests: {0}")]
+    RateLimited(String),
+    #[error("Payload Too Large: {0}")]
+    PayloadTooLarge(String),
+    #[error("Not Acceptable: {0}")]
+    NotAcceptable(String),
+    #[error("Unsupported Media Type: {0}")]
+    UnsupportedMediaType(String),
+    #[error("Unprocessable Entity: {0}")]
+    UnprocessableEntity(String),
+    #[error("Request Timeout: {0}")]
+    RequestTimeout(String),
+    #[error("Service Unavailable: {0}")]
+    ServiceUnavailable(String),
     #[error("Internal server error")]
     InternalServerError(String),
 }
@@ -25,11 +51,52 @@ impl IntoResponse for AppError {
             AppError::InvalidInput(id, msg) => {
                 (StatusCode::BAD_REQUEST, format!("Invalid input: {} - {}", id, msg))
             }
+            AppError::TokenNotFound(id, msg) => {
+                (StatusCode::NOT_FOUND, format!("Token not found: {} - {}", id, msg))
+            }
+            AppError::Forbidden(msg) => {
+                (StatusCode::FORBIDDEN, format!("Forbidden: {}", msg))
+            }
+            AppError::Unauthorized(msg) => {
+                (StatusCode::UNAUTHORIZED, format!("Unauthorized: {}", msg))
+            }
+            AppError::NotFound(msg) => {
+                (StatusCode::NOT_FOUND, format!("Not found: {}", msg))
+            }
+            AppError::UpstreamError(msg) => {
+                (StatusCode::BAD_GATEWAY, format!("Upstream error: {}", msg))
+            }
+            AppError::RateLimited(msg) => {
+                (StatusCode::TOO_MANY_REQUESTS, format!("Too many requests: {}", msg))
+            }
+            AppError::PayloadTooLarge(msg) => {
+                (StatusCode::PAYLOAD_TOO_LARGE, format!("Payload too large: {}", msg))
+            }
+            AppError::NotAcceptable(msg) => {
+                (StatusCode::NOT_ACCEPTABLE, format!("Not acceptable: {}", msg))
+            }
+            AppError::UnsupportedMediaType(msg) => {
+                (StatusCode::UNSUPPORTED_MEDIA_TYPE, format!("Unsupported media type: {}", msg))
+            }
+            AppError::UnprocessableEntity(msg) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, format!("Failed to decode request body: {}", msg))
+            }
+            AppError::RequestTimeout(msg) => {
+                (StatusCode::REQUEST_TIMEOUT, format!("Request timeout: {}", msg))
+            }
+            AppError::ServiceUnavailable(msg) => {
+                (StatusCode::SERVICE_UNAVAILABLE, format!("Service unavailable: {}", msg))
+            }
             AppError::InternalServerError(msg) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, format!("Internal server error: {}", msg))
             }
         };
 
-        (status, Json(serde_json::json!({"error": error_message}))).into_response()
+        let body = ErrorResponse {
+            error: error_message,
+            request_id: RequestId::current().map(|request_id| request_id.0),
+        };
+
+        (status, Json(body)).into_response()
     }
 }
\ No newline at end of file
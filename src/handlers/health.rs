@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use std::sync::Arc;
+use crate::AppState;
+
+//-- liveness ----------------
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses(
+        (status = 200, description = "Process is alive", body = serde_json::Value)
+    )
+)]
+pub async fn health() -> impl IntoResponse {
+    Json(json!({ "status": "ok" }))
+}
+
+//-- readiness: 종료가 시작되면 503을 돌려줘 로드 밸런서가 새 요청을 보내지 않게 합니다 ----------------
+#[utoipa::path(
+    get,
+    path = "/ready",
+    responses(
+        (status = 200, description = "Ready to receive traffic", body = serde_json::Value),
+        (status = 503, description = "Shutting down", body = serde_json::Value)
+    )
+)]
+pub async fn ready(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    if state.shutdown.is_cancelled() {
+        (StatusCode::SERVICE_UNAVAILABLE, Json(json!({ "status": "draining" })))
+    } else {
+        (StatusCode::OK, Json(json!({ "status": "ready" })))
+    }
+}
@@ -1,28 +1,31 @@
-use axum::extract::{Json, Path, Query};
-use crate::models::{Page, BodyItem};
-use crate::AppError;
+use axum::extract::{Path, Query, State};
+use std::sync::Arc;
+use crate::models::{Page, BodyItem, ItemChange};
+use crate::{AppError, AppState};
+use crate::handlers::Payload;
 
 #[utoipa::path(
     get,
     path = "/item/{id}",
     params(
         ("id" = i32, Path, description = "Item id"),
-        ("number" = Option<i32>, Query, description = "Optional number")
+        ("number" = Option<u32>, Query, description = "Page number (default: 1)")
     ),
     responses(
-        (status = 200, description = "Show item details", body = String)
+        (status = 200, description = "Show item details", body = String, content_type = "text/plain"),
+        (status = 400, description = "Item not found", body = ErrorResponse)
     )
     // tags = ["Item"] // 주석 처리
 )]
 pub async fn show_item(Path(id): Path<i32>, Query(params): Query<Page>) -> Result<String, AppError> {
     // format!("Item ID: {}, Page Number: {}", id, params.number)
     match find_item(id).await {
-        Ok(_) => Ok(format!("Item ID: {}, Page Number: {}", id, params.number)),
+        Ok(_) => Ok(format!("Item ID: {}, Page Number: {}", id, params.number())),
         Err(e) => Err(AppError::InvalidInput(id, e.to_string())),
     }
 }
 
-async fn find_item(id: i32) -> Result<(), String> {
+pub(crate) async fn find_item(id: i32) -> Result<(), String> {
     if id == 1 {
         Err("Item Not Found".to_string())
     } else {
@@ -35,10 +38,11 @@ async fn find_item(id: i32) -> Result<(), String> {
     path = "/add-item",
     request_body = BodyItem,
     responses(
-        (status = 201, description = "Item added successfully", body = String)
+        (status = 200, description = "Item added successfully", body = String, content_type = "text/plain")
     )
     // tags = ["Item"] // 주석 처리
 )]
-pub async fn add_item(Json(item): Json<BodyItem>) -> String {
+pub async fn add_item(State(state): State<Arc<AppState>>, Payload(item): Payload<BodyItem>) -> String {
+    state.item_events.publish(ItemChange::Created, item.clone());
     format!("Item added: {}", item.title)
 }
@@ -1,7 +1,20 @@
 pub mod user;
 pub mod item;
 pub mod errors;
+pub mod token;
+pub mod oidc;
+pub mod admin;
+pub mod health;
+pub mod v1;
+pub mod negotiate;
+pub mod ws;
 
 pub use user::*;
 pub use item::*;
-pub use errors::*;
\ No newline at end of file
+pub use errors::*;
+pub use token::*;
+pub use oidc::*;
+pub use admin::*;
+pub use health::*;
+pub use negotiate::*;
+pub use ws::*;
\ No newline at end of file
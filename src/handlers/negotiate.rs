@@ -0,0 +1,136 @@
+// 내용 협상 (content negotiation)
+//
+// 모델을 돌려주는 핸들러는 `AcceptFormat`으로 응답 형식을 고르고 `Negotiated`로 응답합니다.
+//   Accept: application/msgpack | application/cbor | application/json (기본)
+// 모델을 받는 핸들러는 `Json<T>` 대신 `Payload<T>`로 Content-Type에 맞게 본문을 읽습니다.
+// 지원하지 않는 Accept는 406, Content-Type은 415입니다. 오류 본문(ErrorResponse)은 항상 JSON입니다.
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Json, Request},
+    http::{header, request::Parts, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use crate::models::Format;
+use crate::AppError;
+
+/// Accept 헤더로 고른 응답 형식. Accept가 없거나 `*/*`, `application/*`이면 JSON입니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AcceptFormat(pub Format);
+
+impl AcceptFormat {
+    /// q 값이 가장 큰 형식. 같으면 와일드카드보다 구체적인 것, 그다음 먼저 나온 것을 고릅니다.
+    pub fn from_accept(accept: &str) -> Option<Format> {
+        let mut best: Option<(f32, bool, Format)> = None;
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media_type = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let q = parts
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if q <= 0.0 {
+                continue;
+            }
+            let (format, specific) = match media_type.as_str() {
+                "*/*" | "application/*" => (Format::Json, false),
+                other => match Format::from_media_type(other) {
+                    Some(format) => (format, true),
+                    None => continue,
+                },
+            };
+            if best.is_none_or(|(best_q, best_specific, _)| q > best_q || (q == best_q && specific && !best_specific)) {
+                best = Some((q, specific, format));
+            }
+        }
+        best.map(|(_, _, format)| format)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AcceptFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(accept) = parts.headers.get(header::ACCEPT) else {
+            return Ok(AcceptFormat(Format::Json));
+        };
+        let accept = accept.to_str().unwrap_or_default();
+        match AcceptFormat::from_accept(accept) {
+            Some(format) => Ok(AcceptFormat(format)),
+            None => Err(AppError::NotAcceptable(format!(
+                "'{}' (supported: {})",
+                accept,
+                Format::ALL.map(Format::content_type).join(", ")
+            ))),
+        }
+    }
+}
+
+/// `AcceptFormat`으로 고른 형식으로 직렬화하는 응답. 캐시가 형식별로 나뉘도록 `Vary: Accept`를 붙입니다.
+pub struct Negotiated<T>(pub Format, pub T);
+
+impl<T: Serialize> IntoResponse for Negotiated<T> {
+    fn into_response(self) -> Response {
+        let Negotiated(format, value) = self;
+        let mut res = match format {
+            Format::Json => Json(value).into_response(),
+            format => match format.encode(&value) {
+                Ok(bytes) => ([(header::CONTENT_TYPE, format.content_type())], bytes).into_response(),
+                Err(e) => return AppError::InternalServerError(format!("Failed to encode response - {}", e)).into_response(),
+            },
+        };
+        res.headers_mut().append(header::VARY, HeaderValue::from_static("accept"));
+        res
+    }
+}
+
+/// Content-Type에 따라 JSON, MessagePack, CBOR 본문을 읽습니다.
+/// JSON은 `Json<T>`에 그대로 맡기므로 본문 오류(400, 422)는 예전과 같습니다. Content-Type이 없거나 모르는 값이면 415입니다.
+pub struct Payload<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequest<S> for Payload<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        // Json<T>처럼 application/problem+json 같은 +json 형식도 JSON으로 읽습니다.
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        let format = Format::from_media_type(media_type).or_else(|| media_type.ends_with("+json").then_some(Format::Json));
+
+        match format {
+            Some(Format::Json) => {
+                let Json(value) = Json::<T>::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+                Ok(Payload(value))
+            }
+            Some(format) => {
+                let bytes = Bytes::from_request(req, state).await.map_err(IntoResponse::into_response)?;
+                format
+                    .decode(&bytes)
+                    .map(Payload)
+                    .map_err(|e| AppError::UnprocessableEntity(e).into_response())
+            }
+            None => Err(AppError::UnsupportedMediaType(format!(
+                "'{}' (supported: {})",
+                content_type,
+                Format::ALL.map(Format::content_type).join(", ")
+            ))
+            .into_response()),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use axum::{
+    extract::{Extension, Query, State},
+    response::{IntoResponse, Redirect},
+};
+use serde::Deserialize;
+use sqlx::Row;
+use crate::db::{self, DbPool};
+use std::sync::Arc;
+use crate::auth::{self, oidc::{IdTokenClaims, OidcClient, OidcError}};
+use crate::models::{OidcLoginResponse, User, UserChange};
+use crate::metrics::observe_query;
+use crate::{AppError, AppState};
+use crate::handlers::{AcceptFormat, Negotiated};
+
+#[derive(Deserialize)]
+pub struct CallbackParams {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+impl From<OidcError> for AppError {
+    fn from(e: OidcError) -> Self {
+        match e {
+            OidcError::Provider(msg) => AppError::UpstreamError(msg),
+            OidcError::TooManyPendingLogins => AppError::ServiceUnavailable(e.to_string()),
+            e => AppError::Unauthorized(e.to_string()),
+        }
+    }
+}
+
+fn oidc_client(state: &AppState) -> Result<&Arc<OidcClient>, AppError> {
+    state
+        .oidc
+        .as_ref()
+        .ok_or_else(|| AppError::NotFound("OIDC login is not configured".to_string()))
+}
+
+//-- OIDC 로그인 시작 ----------------
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/login",
+    responses(
+        (status = 303, description = "Redirect to the identity provider"),
+        (status = 404, description = "OIDC login is not configured", body = ErrorResponse),
+        (status = 502, description = "Identity provider discovery failed", body = ErrorResponse),
+        (status = 503, description = "Too many logins in progress", body = ErrorResponse)
+    )
+)]
+pub async fn oidc_login(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+    let client = oidc_client(&state)?;
+    let url = client.authorization_url().await?;
+    Ok(Redirect::to(&url))
+}
+
+//-- OIDC callback ----------------
+#[utoipa::path(
+    get,
+    path = "/auth/oidc/callback",
+    params(
+        ("code" = Option<String>, Query, description = "Authorization code"),
+        ("state" = Option<String>, Query, description = "State returned by the identity provider"),
+        ("error" = Option<String>, Query, description = "Error returned by the identity provider")
+    ),
+    responses(
+        (status = 200, description = "Logged in. The raw token is only shown once", body = OidcLoginResponse),
+        (status = 401, description = "Login rejected (invalid state, id token or provider error)", body = ErrorResponse),
+        (status = 404, description = "OIDC login is not configured", body = ErrorResponse),
+        (status = 502, description = "Identity provider request failed", body = ErrorResponse)
+    )
+)]
+pub async fn oidc_callback(
+    AcceptFormat(format): AcceptFormat,
+    State(state): State<Arc<AppState>>,
+    Extension(db_pool): Extension<DbPool>,
+    Query(params): Query<CallbackParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let client = oidc_client(&state)?;
+
+    if let Some(error) = params.error {
+        return Err(AppError::Unauthorized(format!(
+            "identity provider returned '{}' {}",
+            error,
+            params.error_description.unwrap_or_default()
+        )));
+    }
+    let (Some(code), Some(login_state)) = (params.code, params.state) else {
+        return Err(AppError::Unauthorized("missing code or state".to_string()));
+    };
+
+    let claims = client.exchange_code(&code, &login_state).await?;
+
+    let user = upsert_oidc_user(&state, &db_pool, &claims, client.config.link_verified_email).await?;
+
+    // 로그인할 때마다 토큰이 쌓이지 않도록 이전 로그인에서 받은 토큰은 폐기합니다.
+    observe_query(
+        "revoke_oidc_tokens",
+        sqlx::query("UPDATE axum_api_tokens SET revoked_at = ? WHERE user_id = ? AND name = ? AND revoked_at IS NULL")
+            .bind(auth::now_epoch())
+            .bind(user.id)
+            .bind(OIDC_TOKEN_NAME),
+        |q| q.execute(&db_pool),
+    )
+    .await
+    .map_err(|e| AppError::InternalServerError(format!("Failed to revoke previous login tokens - {}", e)))?;
+
+    let token = auth::issue_token(&db_pool, user.id, OIDC_TOKEN_NAME, &client.config.token_scopes)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to issue token - {}", e)))?;
+
+    Ok(Negotiated(format, OidcLoginResponse { user, token }))
+}
+
+/// OIDC 로그인으로 발급하는 토큰의 이름. 다음 로그인 때 같은 이름의 토큰은 폐기됩니다.
+pub const OIDC_TOKEN_NAME: &str = "oidc-login";
+
+/// ID 토큰의 sub로 사용자를 찾고, 없으면 새로 만듭니다.
+/// `link_verified_email`이면 IdP가 확인한 email이 같은 (아직 연결되지 않은) 기존 사용자에 연결합니다.
+async fn upsert_oidc_user(
+    state: &AppState,
+    db_pool: &DbPool,
+    claims: &IdTokenClaims,
+    link_verified_email: bool,
+) -> Result<User, AppError> {
+    let db_err = |e: sqlx::Error| AppError::InternalServerError(format!("Failed to map OIDC user - {}", e));
+    let to_user = |row: sqlx::any::AnyRow| -> Result<User, AppError> {
+        Ok(User {
+            id: row.try_get("id").map_err(db_err)?,
+            name: row.try_get("name").map_err(db_err)?,
+            email: row.try_get("email").map_err(db_err)?,
+        })
+    };
+
+    if let Some(row) = observe_query(
+        "find_user_by_oidc_subject",
+        sqlx::query("SELECT id, name, email FROM axum_users WHERE oidc_subject = ?")
+            .bind(&claims.sub),
+        |q| q.fetch_optional(db_pool),
+    )
+    .await
+    .map_err(db_err)?
+    {
+        return to_user(row);
+    }
+
+    let Some(email) = claims.email.as_deref() else {
+        return Err(AppError::Unauthorized("id token has no email claim".to_string()));
+    };
+
+    let linkable = if link_verified_email && claims.email_verified() {
+        observe_query(
+            "find_user_by_email",
+            sqlx::query("SELECT id, name, email FROM axum_users WHERE email = ? AND oidc_subject IS NULL ORDER BY id")
+                .bind(email),
+            |q| q.fetch_optional(db_pool),
+        )
+        .await
+        .map_err(db_err)?
+    } else {
+        None
+    };
+    if let Some(row) = linkable {
+        let user = to_user(row)?;
+        observe_query(
+            "link_oidc_subject",
+            sqlx::query("UPDATE axum_users SET oidc_subject = ? WHERE id = ?")
+                .bind(&claims.sub)
+                .bind(user.id),
+            |q| q.execute(db_pool),
+        )
+        .await
+        .map_err(db_err)?;
+        return Ok(user);
+    }
+
+    let name = claims.name.clone().unwrap_or_else(|| email.to_string());
+    let sql = db::returning_id("INSERT INTO axum_users (name, email, oidc_subject) VALUES (?, ?, ?)");
+    let id = observe_query(
+        "create_oidc_user",
+        sqlx::query(&sql).bind(&name).bind(email).bind(&claims.sub),
+        |q| db::fetch_insert_id(db_pool, q),
+    )
+    .await
+    .map_err(db_err)?;
+
+    let user = User {
+        id,
+        name,
+        email: email.to_string(),
+    };
+    state.user_events.publish(UserChange::Created, user.clone());
+    Ok(user)
+}
@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use sqlx::Row;
+use crate::db::{self, DbPool};
+use crate::auth::{self, Principal, KNOWN_SCOPES};
+use crate::models::{ApiToken, CreateTokenRequest};
+use crate::AppError;
+use crate::handlers::{AcceptFormat, Negotiated, Payload};
+use crate::metrics::observe_query;
+
+fn ensure_can_manage(principal: &Principal, user_id: i32) -> Result<(), AppError> {
+    if principal.can_manage_tokens_of(user_id) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "not allowed to manage tokens of user {}",
+            user_id
+        )))
+    }
+}
+
+/// 값이 깨진 행을 id 0, 빈 이름으로 보여 주지 않도록 디코딩 오류를 그대로 돌려줍니다.
+fn token_from_row(row: &sqlx::any::AnyRow) -> Result<ApiToken, sqlx::Error> {
+    Ok(ApiToken {
+        id: row.try_get("id")?,
+        user_id: row.try_get("user_id")?,
+        name: row.try_get("name")?,
+        token_prefix: row.try_get("token_prefix")?,
+        scopes: auth::parse_scopes(&row.try_get::<String, _>("scopes")?),
+        created_at: row.try_get("created_at")?,
+        last_used_at: db::get_nullable(row, "last_used_at")?,
+        revoked_at: db::get_nullable(row, "revoked_at")?,
+    })
+}
+
+//-- 개인 액세스 토큰 생성 ----------------
+#[utoipa::path(
+    post,
+    path = "/users/{id}/tokens",
+    params(
+        ("id" = i32, Path, description = "Owner user id")
+    ),
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 201, description = "Token created. The raw token is only shown once", body = CreatedApiToken),
+        (status = 400, description = "Invalid name or scopes", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Not allowed to manage this user's tokens", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn create_token(
+    AcceptFormat(format): AcceptFormat,
+    Extension(db_pool): Extension<DbPool>,
+    Extension(principal): Extension<Principal>,
+    Path(user_id): Path<i32>,
+    Payload(req): Payload<CreateTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    ensure_can_manage(&principal, user_id)?;
+
+    let name = req.name.trim().to_string();
+    if name.is_empty() || name.len() > 100 {
+        return Err(AppError::InvalidInput(
+            user_id,
+            "token name must be 1-100 characters".to_string(),
+        ));
+    }
+    if req.scopes.is_empty() {
+        return Err(AppError::InvalidInput(user_id, "at least one scope is required".to_string()));
+    }
+    if let Some(unknown) = req.scopes.iter().find(|s| !KNOWN_SCOPES.contains(&s.as_str())) {
+        return Err(AppError::InvalidInput(user_id, format!("unknown scope '{}'", unknown)));
+    }
+    // 사용자는 자신이 가진 것보다 넓은 권한의 토큰을 만들 수 없습니다.
+    if let Some(scope) = req.scopes.iter().find(|s| !principal.has_scope(s)) {
+        return Err(AppError::Forbidden(format!("cannot grant scope '{}'", scope)));
+    }
+
+    let created = auth::issue_token(&db_pool, user_id, &name, &req.scopes)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to create token - {}", e)))?;
+
+    Ok((StatusCode::CREATED, Negotiated(format, created)))
+}
+
+//-- 개인 액세스 토큰 목록 ----------------
+#[utoipa::path(
+    get,
+    path = "/users/{id}/tokens",
+    params(
+        ("id" = i32, Path, description = "Owner user id")
+    ),
+    responses(
+        (status = 200, description = "Tokens of the user (without secrets)", body = Vec<ApiToken>),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Not allowed to manage this user's tokens", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn list_tokens(
+    AcceptFormat(format): AcceptFormat,
+    Extension(db_pool): Extension<DbPool>,
+    Extension(principal): Extension<Principal>,
+    Path(user_id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    ensure_can_manage(&principal, user_id)?;
+
+    let rows = observe_query(
+        "list_tokens",
+        sqlx::query(
+            "SELECT id, user_id, name, token_prefix, scopes, created_at, last_used_at, revoked_at \
+             FROM axum_api_tokens WHERE user_id = ? ORDER BY id",
+        )
+        .bind(user_id),
+        |q| q.fetch_all(&db_pool),
+    )
+    .await
+    .map_err(|e| AppError::InternalServerError(format!("Failed to fetch tokens - {}", e)))?;
+
+    let tokens = rows
+        .iter()
+        .map(token_from_row)
+        .collect::<Result<Vec<_>, sqlx::Error>>()
+        .map_err(|e| AppError::InternalServerError(format!("Failed to read tokens - {}", e)))?;
+
+    Ok(Negotiated(format, tokens))
+}
+
+//-- 개인 액세스 토큰 폐기 ----------------
+#[utoipa::path(
+    delete,
+    path = "/users/{id}/tokens/{token_id}",
+    params(
+        ("id" = i32, Path, description = "Owner user id"),
+        ("token_id" = i32, Path, description = "Token id to revoke")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Not allowed to manage this user's tokens", body = ErrorResponse),
+        (status = 404, description = "Token not found or already revoked", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn revoke_token(
+    Extension(db_pool): Extension<DbPool>,
+    Extension(principal): Extension<Principal>,
+    Path((user_id, token_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    ensure_can_manage(&principal, user_id)?;
+
+    let result = observe_query(
+        "revoke_token",
+        sqlx::query(
+            "UPDATE axum_api_tokens SET revoked_at = ? WHERE id = ? AND user_id = ? AND revoked_at IS NULL",
+        )
+        .bind(auth::now_epoch())
+        .bind(token_id)
+        .bind(user_id),
+        |q| q.execute(&db_pool),
+    )
+    .await
+    .map_err(|e| AppError::InternalServerError(format!("Failed to revoke token - {}", e)))?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::TokenNotFound(token_id, "no active token for this user".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
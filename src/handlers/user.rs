@@ -1,28 +1,32 @@
 use axum::{
     body::Body,
     extract::{Extension, Json, Path, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
 };
 // use serde_json::{json, Value};
 use serde_json::json;
-use sqlx::{mysql::MySqlPool, Row};
-use crate::models::{User, UserItem, CreateUserRequest};
+use sqlx::Row;
+use crate::db::DbPool;
+use crate::models::{User, UserChange, UserItem, CreateUserRequest};
 use crate::AppState;
 use std::sync::Arc;
 use crate::AppError;
+use crate::handlers::{AcceptFormat, Negotiated, Payload};
+use crate::metrics::observe_query;
 
 //-- 테스트 코드 ----------------
 #[utoipa::path(
     post,
     path = "/create-user",
     responses(
-        (status = 201, description = "User created successfully", body = UserItem)
+        (status = 201, description = "User created successfully (placeholder, nothing is stored)", body = String, content_type = "text/plain")
     )
 )]
 pub async fn create_user() -> impl IntoResponse {
     Response::builder()
         .status(StatusCode::CREATED)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
         .body(Body::from("User Created Successfully"))
         .unwrap()
 }
@@ -34,19 +38,16 @@ pub async fn create_user() -> impl IntoResponse {
     request_body = CreateUserRequest,
     responses(
         (status = 201, description = "User created successfully in DB", body = serde_json::Value),
-        (status = 500, description = "Failed to create user", body = serde_json::Value)
+        (status = 500, description = "Failed to create user", body = ErrorResponse)
     )
 )]
 pub async fn create_user_db(
-    Extension(db_pool): Extension<MySqlPool>,
-    Json(user_data): Json<CreateUserRequest>,
+    State(state): State<Arc<AppState>>,
+    Extension(db_pool): Extension<DbPool>,
+    Payload(user_data): Payload<CreateUserRequest>,
 // ) -> impl IntoResponse {
 ) -> Result<impl IntoResponse, AppError> {
-    match sqlx::query("INSERT INTO axum_users (name, email) VALUES (?, ?)")
-        .bind(&user_data.name)
-        .bind(&user_data.email)
-        .execute(&db_pool)
-        .await {
+    match super::v1::insert_user(&db_pool, &user_data.name, &user_data.email).await {
             // Ok(_) => (
             //     StatusCode::CREATED,
             //     Json(json!({
@@ -63,16 +64,23 @@ pub async fn create_user_db(
             //         "error": format!("Failed to create user: {}", e)
             //     }))
             // ).into_response(),
-            Ok(_) => Ok((
-                StatusCode::CREATED,
-                Json(json!({
-                    "message": "User Created Successfully",
-                    "user" : {
-                        "name": user_data.name,
-                        "email": user_data.email,
-                    }
-                }))
-            ).into_response()),
+            Ok(id) => {
+                state.user_events.publish(UserChange::Created, User {
+                    id,
+                    name: user_data.name.clone(),
+                    email: user_data.email.clone(),
+                });
+                Ok((
+                    StatusCode::CREATED,
+                    Json(json!({
+                        "message": "User Created Successfully",
+                        "user" : {
+                            "name": user_data.name,
+                            "email": user_data.email,
+                        }
+                    }))
+                ).into_response())
+            }
             Err(e) => Err(AppError::InternalServerError(format!(
                 "Failed to create user - {}", 
                 e
@@ -87,7 +95,7 @@ pub async fn create_user_db(
         (status = 200, description = "List of users", body = Vec<User>)
     )
 )]
-pub async fn list_users() -> impl IntoResponse {
+pub async fn list_users(AcceptFormat(format): AcceptFormat) -> impl IntoResponse {
     let users = vec![
         User {
             id: 1,
@@ -101,7 +109,7 @@ pub async fn list_users() -> impl IntoResponse {
         },
     ];
 
-    Json(users)
+    Negotiated(format, users)
 }
 
 #[utoipa::path(
@@ -112,14 +120,17 @@ pub async fn list_users() -> impl IntoResponse {
     ),
     responses(
         (status = 200, description = "User deleted (placeholder response)", body = UserItem),
-        (status = 500, description = "Failed to delete user", body = String)
+        (status = 404, description = "User can not be deleted", body = ErrorResponse)
     )
     // tags = ["User (Test)"]
 )]
 // pub async fn delete_user(Path(user_id): Path<i32>) -> Result<Json<UserItem>, impl IntoResponse> {
-pub async fn delete_user(Path(user_id): Path<i32>) -> Result<Json<UserItem>, AppError> {
+pub async fn delete_user(
+    AcceptFormat(format): AcceptFormat,
+    Path(user_id): Path<i32>,
+) -> Result<Negotiated<UserItem>, AppError> {
     match perform_delete_user(user_id).await {
-        Ok(_) => Ok(Json(UserItem { 
+        Ok(_) => Ok(Negotiated(format, UserItem { 
             id: user_id,
             name: "".to_string(),
         })),
@@ -145,11 +156,14 @@ async fn perform_delete_user(user_id: i32) -> Result<(), String> {
     path = "/axum-users",
     responses(
         (status = 200, description = "List of users from DB", body = Vec<User>),
-        (status = 500, description = "Failed to fetch users", body = String)
+        (status = 500, description = "Failed to fetch users", body = ErrorResponse)
     )
 )]
-// pub async fn list_users_db(Extension(db_pool): Extension<MySqlPool>) -> impl IntoResponse {
-pub async fn list_users_db(Extension(db_pool): Extension<MySqlPool>) -> Result<impl IntoResponse, AppError> {
+// pub async fn list_users_db(Extension(db_pool): Extension<DbPool>) -> impl IntoResponse {
+pub async fn list_users_db(
+    AcceptFormat(format): AcceptFormat,
+    Extension(db_pool): Extension<DbPool>,
+) -> Result<impl IntoResponse, AppError> {
     // let rows = match sqlx::query("SELECT id, name, email FROM axum_users")
     //     .fetch_all(&db_pool)
     //     .await {
@@ -175,8 +189,8 @@ pub async fn list_users_db(Extension(db_pool): Extension<MySqlPool>) -> Result<i
     
     // (StatusCode::OK, Json(axum_users)).into_response()
 
-    let result = sqlx::query("SELECT id, name, email FROM axum_users")
-        .fetch_all(&db_pool)
+    let result = observe_query("list_users", sqlx::query("SELECT id, name, email FROM axum_users"),
+        |q| q.fetch_all(&db_pool))
         .await;
 
     match result {
@@ -191,7 +205,7 @@ pub async fn list_users_db(Extension(db_pool): Extension<MySqlPool>) -> Result<i
                     }
                 })
                 .collect();
-            Ok((StatusCode::OK, Json(axum_users)).into_response())
+            Ok(Negotiated(format, axum_users))
         }
         Err(e) => Err(AppError::InternalServerError(format!(
             "Failed to fetch users from DB - {}",
@@ -204,10 +218,13 @@ pub async fn list_users_db(Extension(db_pool): Extension<MySqlPool>) -> Result<i
     get,
     path = "/admin/get_app_state",
     responses(
-        (status = 200, description = "Get App State", body = serde_json::Value)
+        (status = 200, description = "Get App State", body = serde_json::Value),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Admin scope required", body = ErrorResponse)
     ),
     security(
-        ("ApiKeyAuth" = [])
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
     )
     // tags = ["Admin"]
 )]
@@ -222,6 +239,7 @@ pub async fn get_app_state(State(state): State<Arc<AppState>>) -> impl IntoRespo
             "db_user": state.db_user.clone(),
             "server_host": state.server_host.clone(),
             "server_port": state.server_port.clone(),
+            "websocket": state.websockets.stats(),
         }))
     )
 }
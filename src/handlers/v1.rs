@@ -0,0 +1,396 @@
+// /api/v1 리소스 경로
+//
+// 예전 경로(/create-user-db, /axum-users, /delete-user/:id, ...)는 그대로 두고
+// `middleware::deprecation`이 Deprecation/Sunset 헤더를 붙입니다. 새 클라이언트는 여기 경로를 씁니다.
+//
+// 이름이 예전 핸들러와 겹치므로 `handlers::*`로 다시 내보내지 않습니다 (`handlers::v1::...`).
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+};
+use futures_util::{stream, StreamExt};
+use sqlx::Row;
+use std::convert::Infallible;
+use std::sync::Arc;
+use crate::db::{self, DbPool};
+use crate::auth::Principal;
+use crate::events::Subscription;
+use crate::models::{BodyItem, CreateTokenRequest, CreateUserRequest, ItemChange, Page, UpdateUserRequest, User, UserChange, UserEvent};
+use crate::handlers::{item, token, AcceptFormat, Negotiated, Payload};
+use crate::{AppError, AppState};
+use crate::metrics::observe_query;
+
+fn user_from_row(row: &sqlx::any::AnyRow) -> Result<User, sqlx::Error> {
+    Ok(User {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        email: row.try_get("email")?,
+    })
+}
+
+/// 사용자를 추가하고 id를 돌려줍니다. 예전 경로(/create-user-db)도 같이 씁니다.
+pub(crate) async fn insert_user(db_pool: &DbPool, name: &str, email: &str) -> Result<i32, sqlx::Error> {
+    let sql = db::returning_id("INSERT INTO axum_users (name, email) VALUES (?, ?)");
+    observe_query("create_user", sqlx::query(&sql).bind(name).bind(email), |q| db::fetch_insert_id(db_pool, q)).await
+}
+
+async fn find_user(db_pool: &DbPool, user_id: i32) -> Result<Option<User>, AppError> {
+    let row = observe_query("get_user", sqlx::query("SELECT id, name, email FROM axum_users WHERE id = ?").bind(user_id),
+        |q| q.fetch_optional(db_pool))
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to fetch user - {}", e)))?;
+    row.as_ref()
+        .map(user_from_row)
+        .transpose()
+        .map_err(|e| AppError::InternalServerError(format!("Failed to read user - {}", e)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users",
+    responses(
+        (status = 200, description = "List of users", body = Vec<User>),
+        (status = 500, description = "Failed to fetch users", body = ErrorResponse)
+    )
+)]
+pub async fn list_users(
+    AcceptFormat(format): AcceptFormat,
+    Extension(db_pool): Extension<DbPool>,
+) -> Result<Negotiated<Vec<User>>, AppError> {
+    let rows = observe_query("list_users", sqlx::query("SELECT id, name, email FROM axum_users ORDER BY id"),
+        |q| q.fetch_all(&db_pool))
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to fetch users from DB - {}", e)))?;
+
+    let users = rows
+        .iter()
+        .map(user_from_row)
+        .collect::<Result<Vec<_>, sqlx::Error>>()
+        .map_err(|e| AppError::InternalServerError(format!("Failed to read users - {}", e)))?;
+    Ok(Negotiated(format, users))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/users",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "User created. Location points to the new user", body = User,
+            headers(("Location" = String, description = "/api/v1/users/{id}"))),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Requires the users:write scope", body = ErrorResponse),
+        (status = 500, description = "Failed to create user", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn create_user(
+    AcceptFormat(format): AcceptFormat,
+    State(state): State<Arc<AppState>>,
+    Extension(db_pool): Extension<DbPool>,
+    Payload(user_data): Payload<CreateUserRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let id = insert_user(&db_pool, &user_data.name, &user_data.email)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to create user - {}", e)))?;
+
+    let user = User { id, name: user_data.name, email: user_data.email };
+    state.user_events.publish(UserChange::Created, user.clone());
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, format!("/api/v1/users/{}", id))],
+        Negotiated(format, user),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}",
+    params(
+        ("id" = i32, Path, description = "User id")
+    ),
+    responses(
+        (status = 200, description = "The user", body = User),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Failed to fetch the user", body = ErrorResponse)
+    )
+)]
+pub async fn get_user(
+    AcceptFormat(format): AcceptFormat,
+    Extension(db_pool): Extension<DbPool>,
+    Path(user_id): Path<i32>,
+) -> Result<Negotiated<User>, AppError> {
+    match find_user(&db_pool, user_id).await? {
+        Some(user) => Ok(Negotiated(format, user)),
+        None => Err(AppError::UserNotFound(user_id, "no such user".to_string())),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/{id}",
+    params(
+        ("id" = i32, Path, description = "User id")
+    ),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "The updated user", body = User),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Requires the users:write scope", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Failed to update the user", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn update_user(
+    AcceptFormat(format): AcceptFormat,
+    State(state): State<Arc<AppState>>,
+    Extension(db_pool): Extension<DbPool>,
+    Path(user_id): Path<i32>,
+    Payload(changes): Payload<UpdateUserRequest>,
+) -> Result<Negotiated<User>, AppError> {
+    let Some(mut user) = find_user(&db_pool, user_id).await? else {
+        return Err(AppError::UserNotFound(user_id, "no such user".to_string()));
+    };
+    if changes.name.is_none() && changes.email.is_none() {
+        return Ok(Negotiated(format, user));
+    }
+    if let Some(name) = changes.name {
+        user.name = name;
+    }
+    if let Some(email) = changes.email {
+        user.email = email;
+    }
+
+    let result = observe_query("update_user", sqlx::query("UPDATE axum_users SET name = ?, email = ? WHERE id = ?")
+        .bind(&user.name)
+        .bind(&user.email)
+        .bind(user_id),
+        |q| q.execute(&db_pool))
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to update user - {}", e)))?;
+    // 조회와 수정 사이에 지워진 경우
+    if result.rows_affected() == 0 {
+        return Err(AppError::UserNotFound(user_id, "no such user".to_string()));
+    }
+
+    state.user_events.publish(UserChange::Updated, user.clone());
+    Ok(Negotiated(format, user))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}",
+    params(
+        ("id" = i32, Path, description = "User id to delete")
+    ),
+    responses(
+        (status = 204, description = "User deleted together with their tokens"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Requires the users:write scope", body = ErrorResponse),
+        (status = 404, description = "User not found", body = ErrorResponse),
+        (status = 500, description = "Failed to delete the user", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn delete_user(
+    State(state): State<Arc<AppState>>,
+    Extension(db_pool): Extension<DbPool>,
+    Path(user_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    // 삭제 알림에 삭제 직전의 값을 싣기 위해 먼저 읽습니다.
+    let Some(user) = find_user(&db_pool, user_id).await? else {
+        return Err(AppError::UserNotFound(user_id, "no such user".to_string()));
+    };
+    let result = observe_query("delete_user", sqlx::query("DELETE FROM axum_users WHERE id = ?").bind(user_id),
+        |q| q.execute(&db_pool))
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to delete user - {}", e)))?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::UserNotFound(user_id, "no such user".to_string()));
+    }
+    state.user_events.publish(UserChange::Deleted, user);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn sse_event(event: &UserEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event(event.change.as_str())
+        .data(serde_json::to_string(event).unwrap_or_default())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/events",
+    params(
+        ("Last-Event-ID" = Option<u64>, Header, description = "Resume after this event id (sent automatically by EventSource)")
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events. `created`, `updated` and `deleted` carry a UserEvent as data; \
+            `resync` means the requested events are gone and the user list must be reloaded",
+            body = String, content_type = "text/event-stream"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Requires the users:read scope", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn user_events(State(state): State<Arc<AppState>>, headers: HeaderMap) -> impl IntoResponse {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    let Subscription { resync, missed, live } = state.user_events.subscribe(last_event_id);
+
+    // resync의 id는 지금까지의 마지막 id이므로, 다음 재연결은 그 이후부터 받습니다.
+    let resync = resync.map(|last_id| {
+        Event::default().id(last_id.to_string()).event("resync").data(format!("{{\"last_id\":{}}}", last_id))
+    });
+    let replayed = stream::iter(resync.into_iter().chain(missed.into_iter().map(|event| sse_event(&event))));
+    let live = stream::unfold(live, |mut live| async move {
+        // 뒤처지면(Lagged) 연결을 끊습니다. 클라이언트는 Last-Event-ID로 다시 연결해 버퍼에서 받습니다.
+        let event = live.recv().await.ok()?;
+        Some((sse_event(&event), live))
+    });
+    let events = replayed
+        .chain(live)
+        .map(Ok::<_, Infallible>)
+        // 종료 신호를 받으면 스트림을 닫아 graceful shutdown이 기다리지 않게 합니다.
+        .take_until(state.shutdown.clone().cancelled_owned());
+
+    let keep_alive = KeepAlive::new().interval(state.user_events.config.keep_alive).text("keep-alive");
+    // nginx 등 앞단 프록시가 응답을 모아 두지 않도록
+    ([("x-accel-buffering", "no")], Sse::new(events).keep_alive(keep_alive))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/items/{id}",
+    params(
+        ("id" = i32, Path, description = "Item id"),
+        ("number" = Option<u32>, Query, description = "Page number (default: 1)")
+    ),
+    responses(
+        (status = 200, description = "Show item details", body = String, content_type = "text/plain"),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_item(Path(id): Path<i32>, Query(params): Query<Page>) -> Result<String, AppError> {
+    item::find_item(id)
+        .await
+        .map_err(|e| AppError::NotFound(format!("item {} - {}", id, e)))?;
+    Ok(format!("Item ID: {}, Page Number: {}", id, params.number()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/items",
+    request_body = BodyItem,
+    responses(
+        (status = 201, description = "Item added", body = BodyItem)
+    )
+)]
+pub async fn create_item(
+    AcceptFormat(format): AcceptFormat,
+    State(state): State<Arc<AppState>>,
+    Payload(item): Payload<BodyItem>,
+) -> impl IntoResponse {
+    state.item_events.publish(ItemChange::Created, item.clone());
+    (StatusCode::CREATED, Negotiated(format, item))
+}
+
+//-- 개인 액세스 토큰: 동작은 예전 경로와 같습니다 ----------------
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/tokens",
+    params(
+        ("id" = i32, Path, description = "Owner user id")
+    ),
+    request_body = CreateTokenRequest,
+    responses(
+        (status = 201, description = "Token created. The raw token is only shown once", body = CreatedApiToken),
+        (status = 400, description = "Invalid name or scopes", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Not allowed to manage this user's tokens", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn create_token(
+    format: AcceptFormat,
+    db_pool: Extension<DbPool>,
+    principal: Extension<Principal>,
+    user_id: Path<i32>,
+    req: Payload<CreateTokenRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    token::create_token(format, db_pool, principal, user_id, req).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}/tokens",
+    params(
+        ("id" = i32, Path, description = "Owner user id")
+    ),
+    responses(
+        (status = 200, description = "Tokens of the user (without secrets)", body = Vec<ApiToken>),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Not allowed to manage this user's tokens", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn list_tokens(
+    format: AcceptFormat,
+    db_pool: Extension<DbPool>,
+    principal: Extension<Principal>,
+    user_id: Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    token::list_tokens(format, db_pool, principal, user_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}/tokens/{token_id}",
+    params(
+        ("id" = i32, Path, description = "Owner user id"),
+        ("token_id" = i32, Path, description = "Token id to revoke")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 403, description = "Not allowed to manage this user's tokens", body = ErrorResponse),
+        (status = 404, description = "Token not found or already revoked", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn revoke_token(
+    db_pool: Extension<DbPool>,
+    principal: Extension<Principal>,
+    ids: Path<(i32, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    token::revoke_token(db_pool, principal, ids).await
+}
@@ -0,0 +1,40 @@
+use axum::{
+    extract::{ws::WebSocketUpgrade, Extension, State},
+    response::Response,
+};
+use std::sync::Arc;
+use crate::auth::Principal;
+use crate::{ws, AppError, AppState};
+
+//-- 실시간 변경 알림 (프로토콜은 `crate::ws` 참고) ----------------
+#[utoipa::path(
+    get,
+    path = "/ws",
+    responses(
+        (status = 101, description = "Switching to WebSocket. Send {\"type\":\"subscribe\",\"topics\":[\"users\",\"items\"]}; \
+            the server answers with WsMessage text frames"),
+        (status = 400, description = "Not a WebSocket upgrade request", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing or invalid credentials", body = ErrorResponse),
+        (status = 503, description = "Too many WebSocket connections", body = ErrorResponse)
+    ),
+    security(
+        ("ApiKeyAuth" = []),
+        ("BearerAuth" = [])
+    )
+)]
+pub async fn ws_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(principal): Extension<Principal>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let Some(connection) = state.websockets.try_connect() else {
+        return Err(AppError::ServiceUnavailable(format!(
+            "too many WebSocket connections (max {})",
+            state.websockets.config.max_connections
+        )));
+    };
+    // 구독 요청만 받으므로 큰 메시지는 필요 없습니다.
+    Ok(upgrade
+        .max_message_size(64 * 1024)
+        .on_upgrade(move |socket| ws::serve(socket, state, principal, connection)))
+}
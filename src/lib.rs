@@ -1,11 +1,29 @@
+pub mod auth;
+pub mod cli;
+pub mod db;
+pub mod events;
 pub mod handlers;
+pub mod listener;
+pub mod metrics;
 pub mod middleware;
 pub mod models;
+pub mod openapi;
+pub mod server;
+pub mod telemetry;
+pub mod tls;
+pub mod ws;
 
-use sqlx::mysql::MySqlPool;
+use axum::{
+    routing::{delete, get, patch, post},
+    Router,
+};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 use std::sync::Arc;
 use std::env;
 use dotenvy::dotenv;
+use auth::oidc::{OidcClient, OidcConfig};
+use tokio_util::sync::CancellationToken;
 
 pub use handlers::*;
 pub use middleware::*;
@@ -19,50 +37,282 @@ pub struct AppState {
     pub server_host: String,
     pub server_port: String,
     pub admin_api_key: String,
+    pub oidc: Option<Arc<OidcClient>>,
+    pub rate_limiter: middleware::RateLimiter,
+    pub trusted_proxies: middleware::TrustedProxies,
+    /// 종료 신호를 받으면 취소됩니다. 백그라운드 작업, 스트리밍 응답은 이 토큰을 보고 정리합니다.
+    pub shutdown: CancellationToken,
+    pub admin_client_cert: tls::ClientCertPolicy,
+    /// 사용자 변경 알림 (SSE, WebSocket)
+    pub user_events: events::UserEvents,
+    /// 아이템 추가 알림 (WebSocket)
+    pub item_events: events::ItemEvents,
+    pub websockets: Arc<ws::WsHub>,
 }
 
+#[derive(Clone)]
 pub struct AppConfig {
-    pub db_pool: MySqlPool,
+    pub db_pool: db::DbPool,
     pub app_state: Arc<AppState>,
     pub host: String,
     pub port: String,
+    pub cors: middleware::CorsConfig,
+    pub limits: Arc<middleware::LimitsConfig>,
+    pub compression: middleware::CompressionConfig,
+    pub shutdown: server::ShutdownConfig,
+    pub tls: Option<tls::TlsConfig>,
+    pub deprecation: Arc<middleware::DeprecationConfig>,
+    pub metrics: metrics::MetricsConfig,
 }
 
+/// 서버용: 로그/trace를 설정하고 `load_config`로 설정을 읽습니다.
 pub async fn init_app() -> Result<AppConfig, Box<dyn std::error::Error>> {
     dotenv().ok();
-    let db_name = env::var("DB_NAME").expect("DB_NAME must be set");
-    let db_user = env::var("DB_USER").expect("DB_USER must be set");
-    let db_password = env::var("DB_PASSWORD").expect("DB_PASSWORD must be set");
-    let db_host = env::var("DB_HOST").expect("DB_HOST must be set");
-    let db_port = env::var("DB_PORT").expect("DB_PORT must be set");
-    let db_url = format!("mysql://{}:{}@{}:{}/{}", db_user, db_password, db_host, db_port, db_name);
-
-    let admin_api_key = env::var("ADMIN_API_KEY").expect("ADMIN_API_KEY must be set");
+    telemetry::init_tracing();
+    load_config().await
+}
+
+/// DB 접속 정보. DATABASE_URL이 있으면 그대로 쓰고 (mysql://, sqlite:), 없으면 DB_* 로 MySQL URL을 만듭니다.
+struct DbSettings {
+    name: String,
+    user: String,
+    host: String,
+    port: String,
+    url: String,
+}
+
+impl DbSettings {
+    fn from_env() -> Result<Self, String> {
+        let database_url = env::var("DATABASE_URL").ok().filter(|url| !url.is_empty());
+        let db_var = |name: &str| match &database_url {
+            Some(_) => Ok(env::var(name).unwrap_or_default()),
+            None => env::var(name).map_err(|_| format!("{} must be set (or DATABASE_URL)", name)),
+        };
+        let name = db_var("DB_NAME")?;
+        let user = db_var("DB_USER")?;
+        let password = db_var("DB_PASSWORD")?;
+        let host = db_var("DB_HOST")?;
+        let port = db_var("DB_PORT")?;
+        let url = database_url
+            .clone()
+            .unwrap_or_else(|| format!("mysql://{}:{}@{}:{}/{}", user, password, host, port, name));
+        Ok(DbSettings { name, user, host, port, url })
+    }
+
+    async fn connect(&self) -> Result<db::DbPool, String> {
+        db::connect(&self.url).await.map_err(|e| format!("Failed to connect to DB: {}", e))
+    }
+}
+
+/// DB 설정만 읽고 연결합니다. DB만 쓰는 CLI 명령은 나머지 서버 설정 없이도 돌아갑니다.
+pub async fn connect_db() -> Result<db::DbPool, Box<dyn std::error::Error>> {
+    dotenv().ok();
+    Ok(DbSettings::from_env()?.connect().await?)
+}
+
+/// 환경 변수(.env 포함)로 설정을 읽고 DB에 연결합니다. 로그 설정은 하지 않으므로 CLI 명령도 같이 씁니다.
+pub async fn load_config() -> Result<AppConfig, Box<dyn std::error::Error>> {
+    dotenv().ok();
+    let db_settings = DbSettings::from_env()?;
+
+    let admin_api_key = env::var("ADMIN_API_KEY").map_err(|_| "ADMIN_API_KEY must be set")?;
     let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
     let port = env::var("PORT").unwrap_or_else(|_| "3000".to_string());
+    port.parse::<u16>().map_err(|_| format!("PORT must be a port number, got '{}'", port))?;
+    let oidc = OidcConfig::from_env()?.map(|config| Arc::new(OidcClient::new(config)));
+    let tls_config = tls::TlsConfig::from_env()?;
+    let admin_client_cert = tls::ClientCertPolicy::from_env();
+    if admin_client_cert.required_for_admin && tls_config.as_ref().and_then(|t| t.client_ca_path.as_ref()).is_none() {
+        return Err("ADMIN_REQUIRE_CLIENT_CERT requires TLS_CERT_PATH, TLS_KEY_PATH and TLS_CLIENT_CA_PATH".into());
+    }
     
     let app_state = Arc::new(AppState {
-        db_name: db_name.clone(),
-        db_user: db_user.clone(),
-        db_host: db_host.clone(),
-        db_port: db_port.clone(),
+        db_name: db_settings.name.clone(),
+        db_user: db_settings.user.clone(),
+        db_host: db_settings.host.clone(),
+        db_port: db_settings.port.clone(),
         server_host: host.clone(),
         server_port: port.clone(),
         admin_api_key: admin_api_key.clone(),
+        oidc,
+        rate_limiter: middleware::RateLimiter::new(middleware::RateLimitConfig::from_env()?),
+        trusted_proxies: middleware::TrustedProxies::from_env()?,
+        shutdown: CancellationToken::new(),
+        admin_client_cert,
+        user_events: events::UserEvents::new(events::UserEventsConfig::from_env()),
+        item_events: events::ItemEvents::default(),
+        websockets: Arc::new(ws::WsHub::new(ws::WsConfig::from_env())),
     });
 
-    let db_pool = MySqlPool::connect(&db_url)
-        .await
-        .expect("Failed to connect to DB");
+    let cors = middleware::CorsConfig::from_env()?;
+    let limits = Arc::new(middleware::LimitsConfig::from_env()?);
+    let compression = middleware::CompressionConfig::from_env()?;
+    let shutdown = server::ShutdownConfig::from_env()?;
+    let deprecation = Arc::new(middleware::DeprecationConfig::from_env()?);
+    let metrics = metrics::MetricsConfig::from_env()?;
+
+    // 설정 오류를 먼저 알리도록 DB 연결은 마지막에 합니다.
+    let db_pool = db_settings.connect().await?;
 
     Ok(AppConfig {
         db_pool,
         app_state,
         host,
         port,
+        cors,
+        limits,
+        compression,
+        shutdown,
+        tls: tls_config,
+        deprecation,
+        metrics,
     })
 }
 
+/// 전체 라우터 (라우트, 미들웨어, Swagger UI). 바이너리와 통합 테스트가 같은 앱을 씁니다.
+///
+/// 요청마다 `ConnectInfo<SocketAddr>`가 필요하므로 `server::serve`로 띄우거나, 테스트에서 직접 넣어 줍니다.
+pub fn build_router(config: AppConfig) -> Router {
+    let shared_state = config.app_state.clone();
+
+    // route_layer는 나중에 추가한 것이 바깥쪽: auth_middleware -> require_admin_middleware 순서로 실행
+    let admin_routes = Router::new()
+        .route("/get_app_state", get(handlers::get_app_state))
+        .route("/slow-events", get(handlers::list_slow_events))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::require_admin_middleware,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth_middleware,
+        )); // .with_state(shared_state.clone()) 제거됨
+
+    // 개인 액세스 토큰 관리: 관리자 키 또는 `tokens` 스코프를 가진 본인 토큰으로 접근
+    let token_routes = Router::new()
+        .route("/users/:id/tokens", post(handlers::create_token).get(handlers::list_tokens))
+        .route("/users/:id/tokens/:token_id", delete(handlers::revoke_token))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    // 실시간 알림. 연결 수 제한은 핸들러가 업그레이드 전에 확인합니다.
+    let ws_routes = Router::new()
+        .route("/ws", get(handlers::ws_handler))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    // 버전 없는 예전 경로. 동작은 그대로이고 Deprecation/Sunset/Link 헤더만 붙습니다.
+    let legacy_routes = Router::new()
+        .route("/create-user", post(handlers::create_user))
+        .route("/create-user-db", post(handlers::create_user_db))
+        .route("/users", get(handlers::list_users))
+        .route("/axum-users", get(handlers::list_users_db))
+        .route("/item/:id", get(handlers::show_item))
+        .route("/add-item", post(handlers::add_item))
+        .route("/delete-user/:id", delete(handlers::delete_user))
+        .merge(token_routes)
+        .route_layer(axum::middleware::from_fn_with_state(
+            config.deprecation.clone(),
+            middleware::deprecation_middleware,
+        ));
+
+    let v1_token_routes = Router::new()
+        .route("/users/:id/tokens", post(handlers::v1::create_token).get(handlers::v1::list_tokens))
+        .route("/users/:id/tokens/:token_id", delete(handlers::v1::revoke_token))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    // 사용자 생성/수정/삭제: 관리자 키 또는 `users:write` 스코프 토큰
+    let v1_user_write_routes = Router::new()
+        .route("/users", post(handlers::v1::create_user))
+        .route("/users/:id", patch(handlers::v1::update_user).delete(handlers::v1::delete_user))
+        .route_layer(axum::middleware::from_fn(middleware::require_users_write_middleware))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    // 사용자 변경 알림: /ws의 users 토픽과 같이 `users:read` 스코프가 필요합니다.
+    let v1_user_events_routes = Router::new()
+        .route("/users/events", get(handlers::v1::user_events))
+        .route_layer(axum::middleware::from_fn(middleware::require_users_read_middleware))
+        .route_layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::auth_middleware,
+        ));
+
+    let v1_routes = Router::new()
+        .route("/users", get(handlers::v1::list_users))
+        .route("/users/:id", get(handlers::v1::get_user))
+        .route("/items", post(handlers::v1::create_item))
+        .route("/items/:id", get(handlers::v1::get_item))
+        .merge(v1_user_write_routes)
+        .merge(v1_user_events_routes)
+        .merge(v1_token_routes);
+
+    // SwaggerUi 객체를 생성. 버전마다 문서가 따로 있고, 상단 선택 상자로 바꿔 볼 수 있습니다.
+    let swagger_route: SwaggerUi = SwaggerUi::new("/swagger-ui")
+        .url("/api-docs/v1/openapi.json", openapi::ApiDocV1::openapi())
+        .url("/api-docs/openapi.json", openapi::ApiDoc::openapi());
+
+    // Prometheus 메트릭. METRICS_REQUIRE_ADMIN_KEY=true 이면 관리자 인증 필요
+    let mut metrics_routes = Router::new().route("/metrics", get(metrics::metrics_handler));
+    if config.metrics.require_admin_key {
+        metrics_routes = metrics_routes
+            .route_layer(axum::middleware::from_fn_with_state(
+                shared_state.clone(),
+                middleware::require_admin_middleware,
+            ))
+            .route_layer(axum::middleware::from_fn_with_state(
+                shared_state.clone(),
+                middleware::auth_middleware,
+            ));
+    }
+
+    Router::new()
+        .merge(swagger_route) 
+        .route("/", get(|| async { "hello, Rust!" }))
+        .route("/health", get(handlers::health))
+        .route("/ready", get(handlers::ready))
+        .route("/auth/oidc/login", get(handlers::oidc_login))
+        .route("/auth/oidc/callback", get(handlers::oidc_callback))
+        .merge(legacy_routes)
+        .merge(ws_routes)
+        .nest("/api/v1", v1_routes)
+        .merge(metrics_routes)
+        .nest("/admin", admin_routes)
+        // 본문 크기/처리 시간 제한. rate limit을 통과한 요청만 본문을 읽습니다.
+        // 인증(`route_layer`)은 이 레이어 안쪽에서 실행되므로, 인증에 실패할 요청도 제한 크기까지는 본문을 읽습니다.
+        .layer(axum::middleware::from_fn_with_state(
+            config.limits.clone(),
+            middleware::limits_middleware,
+        ))
+        .layer(axum::extract::DefaultBodyLimit::disable())
+        // gzip 요청 본문은 크기 제한을 검사하기 전에 풉니다.
+        .layer(config.compression.decompression_layer())
+        .layer(config.compression.layer())
+        .layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::rate_limit_middleware,
+        ))
+        .layer(config.cors.layer())
+        .layer(axum::middleware::from_fn(metrics::metrics_middleware))
+        .layer(axum::middleware::from_fn(middleware::logging_middleware))
+        .layer(axum::middleware::from_fn_with_state(
+            shared_state.clone(),
+            middleware::client_ip_middleware,
+        ))
+        .layer(axum::middleware::from_fn(middleware::request_id_middleware))
+        .layer(axum::extract::Extension(config.db_pool))
+        .with_state(shared_state) // 최종적으로 전체 라우터에 상태 적용
+}
+
 // 결론부터 말씀드리면, sqlx::Pool은 일반적으로 명시적으로 닫아줄 필요가 없습니다.
 // 그 이유는 다음과 같습니다:
 // 1] Drop Trait 구현: sqlx::Pool 타입은 Rust의 Drop 트레잇을 구현하고 있습니다. 
@@ -74,3 +324,5 @@ pub async fn init_app() -> Result<AppConfig, Box<dyn std::error::Error>> {
 //  - 현재 사용 중이지 않은 유휴(idle) 커넥션들을 닫습니다.
 //  - 만약 현재 사용 중인 커넥션이 있다면, 해당 작업이 완료될 때까지 잠시 기다린 후 닫습니다. 
 //    (내부적으로 타임아웃이 있을 수 있습니다.)
+// 3] 그래도 main에서는 종료 직전에 `db_pool.close().await`를 호출합니다.
+//  Drop은 기다려 주지 않으므로, 명시적으로 닫아야 커넥션이 DB 쪽에서 정상 종료(COM_QUIT)됩니다.
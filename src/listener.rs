@@ -0,0 +1,201 @@
+// 서버 리스너: TCP, Unix 도메인 소켓, systemd 소켓 활성화
+//
+// 우선순위:
+//   1. systemd가 넘겨준 소켓 (LISTEN_PID가 이 프로세스이고 LISTEN_FDS >= 1). 첫 번째 fd(3)를 사용합니다.
+//      TCP/Unix 소켓 모두 가능하며, 종류는 소켓 주소로 판단합니다. LISTEN_* 환경 변수는 main이 tokio runtime을
+//      만들기 전에 `take_systemd_socket`으로 읽고 지운 뒤 `Listener::bind`에 넘깁니다.
+//   2. LISTEN_UNIX_PATH가 있으면 그 경로에 Unix 소켓을 만듭니다. 남아 있던 소켓 파일은 지우고 다시 만들며,
+//      권한은 LISTEN_UNIX_MODE(8진수, 기본값 660)로 설정합니다. 종료할 때 파일을 지웁니다.
+//   3. 그 외에는 HOST:PORT TCP
+//
+// Unix 소켓 연결은 원격 IP가 없으므로 127.0.0.1로 간주합니다.
+// 앞단 리버스 프록시의 X-Forwarded-For를 믿으려면 TRUSTED_PROXIES=127.0.0.1/32 를 설정합니다.
+//
+// 환경 변수 예:
+//   LISTEN_UNIX_PATH=/run/axum-rest-api/api.sock
+//   LISTEN_UNIX_MODE=660
+use std::env;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+/// `server::serve`가 연결을 받는 방법. 원격 주소는 `ConnectInfo<SocketAddr>`로 요청에 들어갑니다.
+pub trait Accept: Send {
+    type Io: AsyncRead + AsyncWrite + Unpin + Send + 'static;
+
+    fn accept(&self) -> impl Future<Output = io::Result<(Self::Io, SocketAddr)>> + Send;
+}
+
+impl<A: Accept + Sync> Accept for &A {
+    type Io = A::Io;
+
+    fn accept(&self) -> impl Future<Output = io::Result<(Self::Io, SocketAddr)>> + Send {
+        (**self).accept()
+    }
+}
+
+impl Accept for TcpListener {
+    type Io = TcpStream;
+
+    async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        let (stream, addr) = TcpListener::accept(self).await?;
+        let _ = stream.set_nodelay(true);
+        Ok((stream, addr))
+    }
+}
+
+#[cfg(unix)]
+impl Accept for UnixListener {
+    type Io = UnixStream;
+
+    async fn accept(&self) -> io::Result<(UnixStream, SocketAddr)> {
+        let (stream, _) = UnixListener::accept(self).await?;
+        Ok((stream, SocketAddr::from(([127, 0, 0, 1], 0))))
+    }
+}
+
+pub enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        /// 직접 만든 소켓 파일. systemd가 넘겨준 소켓이면 `None`
+        path: Option<PathBuf>,
+    },
+}
+
+impl fmt::Display for Listener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Listener::Tcp(listener) => match listener.local_addr() {
+                Ok(addr) => write!(f, "tcp://{}", addr),
+                Err(_) => write!(f, "tcp://?"),
+            },
+            #[cfg(unix)]
+            Listener::Unix { listener, .. } => {
+                let path = listener.local_addr().ok().and_then(|a| a.as_pathname().map(|p| p.display().to_string()));
+                write!(f, "unix://{}", path.unwrap_or_else(|| "?".to_string()))
+            }
+        }
+    }
+}
+
+/// systemd가 넘겨준 리스닝 소켓. `take_systemd_socket`으로만 만들 수 있습니다.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct SystemdSocket {
+    fd: i32,
+    /// LISTEN_FDS. 첫 번째 소켓만 씁니다.
+    count: i32,
+}
+
+/// systemd 소켓 활성화 (sd_listen_fds). 이 프로세스에 넘겨진 소켓이 없으면 `None`
+///
+/// sd_listen_fds(unset_environment=1)처럼 LISTEN_PID, LISTEN_FDS, LISTEN_FDNAMES를 지워서
+/// 자식 프로세스가 같은 fd를 자기 것으로 여기지 않게 합니다.
+///
+/// # Safety
+///
+/// 환경 변수를 지우므로, 다른 스레드가 없을 때(tokio runtime이나 tracing exporter를 만들기 전) 불러야 합니다.
+#[cfg(unix)]
+pub unsafe fn take_systemd_socket() -> Option<SystemdSocket> {
+    const SD_LISTEN_FDS_START: i32 = 3;
+
+    // 다른 프로세스에 넘겨진 환경 변수를 물려받은 경우를 걸러 냅니다.
+    let for_us = env::var("LISTEN_PID").ok().and_then(|pid| pid.parse::<u32>().ok()) == Some(std::process::id());
+    let count = env::var("LISTEN_FDS").ok().and_then(|n| n.parse::<i32>().ok()).unwrap_or(0);
+    if !for_us || count < 1 {
+        return None;
+    }
+    // SAFETY: 호출자가 다른 스레드가 없음을 보장합니다.
+    unsafe {
+        env::remove_var("LISTEN_PID");
+        env::remove_var("LISTEN_FDS");
+        env::remove_var("LISTEN_FDNAMES");
+    }
+    Some(SystemdSocket { fd: SD_LISTEN_FDS_START, count })
+}
+
+impl Listener {
+    /// 위의 우선순위대로 리스너를 만듭니다.
+    pub async fn bind(host: &str, port: &str, systemd: Option<SystemdSocket>) -> io::Result<Listener> {
+        #[cfg(unix)]
+        {
+            if let Some(socket) = systemd {
+                return systemd_listener(socket);
+            }
+            if let Ok(path) = env::var("LISTEN_UNIX_PATH")
+                && !path.is_empty()
+            {
+                return bind_unix(PathBuf::from(path));
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = systemd;
+
+        let listener = TcpListener::bind(format!("{}:{}", host, port)).await?;
+        Ok(Listener::Tcp(listener))
+    }
+
+    /// 직접 만든 Unix 소켓 파일을 지웁니다.
+    pub fn cleanup(&self) {
+        #[cfg(unix)]
+        if let Listener::Unix { path: Some(path), .. } = self
+            && let Err(e) = std::fs::remove_file(path)
+        {
+            tracing::warn!(path = %path.display(), error = %e, "failed to remove unix socket file");
+        }
+    }
+}
+
+#[cfg(unix)]
+fn bind_unix(path: PathBuf) -> io::Result<Listener> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    // 이전 실행에서 남은 소켓 파일만 지웁니다. 일반 파일이면 실수일 수 있으므로 그대로 둡니다(bind 실패).
+    if let Ok(meta) = std::fs::symlink_metadata(&path)
+        && meta.file_type().is_socket()
+    {
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    let mode = env::var("LISTEN_UNIX_MODE")
+        .ok()
+        .map(|v| u32::from_str_radix(&v, 8).expect("LISTEN_UNIX_MODE must be an octal mode like 660"))
+        .unwrap_or(0o660);
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode))?;
+
+    Ok(Listener::Unix { listener, path: Some(path) })
+}
+
+/// `take_systemd_socket`이 넘겨준 fd로 리스너를 만듭니다.
+#[cfg(unix)]
+fn systemd_listener(systemd: SystemdSocket) -> io::Result<Listener> {
+    use std::os::fd::FromRawFd;
+
+    if systemd.count > 1 {
+        tracing::warn!(count = systemd.count, "systemd passed more than one socket, only the first one is used");
+    }
+
+    // SAFETY: `SystemdSocket`은 LISTEN_PID가 이 프로세스일 때만 만들어지므로 fd는 systemd가 넘겨준 리스닝 소켓이고,
+    // 값으로 한 번만 넘겨받으므로 다른 곳에서 소유하지 않습니다.
+    let socket = unsafe { socket2::Socket::from_raw_fd(systemd.fd) };
+    socket.set_nonblocking(true)?;
+    let local_addr = socket.local_addr()?;
+
+    if local_addr.as_socket().is_some() {
+        let listener = TcpListener::from_std(socket.into())?;
+        Ok(Listener::Tcp(listener))
+    } else if local_addr.is_unix() {
+        let listener = UnixListener::from_std(socket.into())?;
+        Ok(Listener::Unix { listener, path: None })
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidInput, "systemd socket is neither TCP nor Unix"))
+    }
+}
@@ -1,57 +1,5 @@
-use axum::{
-    // routing::{get_service, MethodRouter}, // Axum 0.7+ 스타일, 일단 주석
-    Router,
-    routing::{delete, get, post}
-};
-use axum_rest_api::{handlers, middleware, init_app, models};
-use utoipa::OpenApi;
-use utoipa::Modify; // Modify 트레잇 임포트
-use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme}; // ApiKeyValue 임포트 확인
-use utoipa_swagger_ui::SwaggerUi;
-
-struct SecurityAddon;
-
-impl Modify for SecurityAddon {
-    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
-        let components = openapi.components.get_or_insert_with(Default::default);
-        components.add_security_scheme(
-            "ApiKeyAuth", // 보안 스키마 이름
-            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Admin-API-Key"))), // ApiKeyValue 사용
-        );
-    }
-}
-
-#[derive(OpenApi)]
-#[openapi(
-    paths(
-        // 여기에 핸들러 함수들을 등록합니다.
-        handlers::user::create_user,
-        handlers::user::create_user_db,
-        handlers::user::list_users,
-        handlers::user::list_users_db,
-        handlers::user::delete_user,
-        handlers::user::get_app_state,
-        handlers::item::show_item,
-        handlers::item::add_item,
-    ),
-    components(
-        schemas(
-            // 여기에 API에서 사용하는 모델(스키마)들을 등록합니다.
-            models::User,
-            models::UserItem,
-            models::Page,
-            models::BodyItem,
-            models::CreateUserRequest,
-        )
-        // security_schemes 직접 정의 제거
-    ),
-    modifiers(&SecurityAddon), // modifiers를 사용하여 보안 스키마 추가
-    tags(
-        (name = "axum-rest-api", description = "Axum REST API endpoints")
-    )
-    // security(...) // 전역 보안 요구사항은 여기서 정의 가능
-)]
-struct ApiDoc;
+use axum_rest_api::listener::{Listener, SystemdSocket};
+use axum_rest_api::{build_router, cli, init_app, server, telemetry, tls};
 
 //--- tokio main ----------------
 // https://www.twilio.com/en-us/blog/build-high-performance-rest-apis-rust-axum
@@ -67,65 +15,88 @@ struct ApiDoc;
 // http://localhost:3000/api-docs/openapi.json
 //---------------------------------
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let config = init_app().await?;
-    let shared_state = config.app_state.clone(); 
-
-    let admin_routes = Router::new()
-        .route("/get_app_state", get(handlers::get_app_state)
-            .route_layer(axum::middleware::from_fn_with_state(
-                shared_state.clone(),
-                middleware::auth_middleware,
-            ))
-        ); // .with_state(shared_state.clone()) 제거됨
-
-    // SwaggerUi 객체를 생성 
-    let swagger_route: SwaggerUi = SwaggerUi::new("/swagger-ui")
-        .url("/api-docs/openapi.json", ApiDoc::openapi())
-        .into();
-
-    let app = Router::new()
-        .merge(swagger_route) 
-        .route("/", get(|| async { "hello, Rust!" }))
-        .route("/create-user", post(handlers::create_user))
-        .route("/create-user-db", post(handlers::create_user_db))
-        .route("/users", get(handlers::list_users))
-        .route("/axum-users", get(handlers::list_users_db))
-        .route("/item/:id", get(handlers::show_item))
-        .route("/add-item", post(handlers::add_item))
-        .route("/delete-user/:id", delete(handlers::delete_user))
-        .nest("/admin", admin_routes)
-        .layer(axum::middleware::from_fn(middleware::logging_middleware))
-        .layer(axum::extract::Extension(config.db_pool))
-        .with_state(shared_state); // 최종적으로 전체 라우터에 상태 적용
-
-    let addr_str = format!("{}:{}", config.host, config.port);
-    let listener = tokio::net::TcpListener::bind(&addr_str).await?; 
-    println!("서버가 http://{} 에서 실행 중입니다", listener.local_addr()?); 
-
-    // app을 ConnectInfo를 제공하는 서비스로 변환
-    let app_with_connect_info = app.into_make_service_with_connect_info::<std::net::SocketAddr>();
+// tokio runtime은 직접 만듭니다. systemd 환경 변수는 다른 스레드가 생기기 전에 지워야 하기 때문입니다.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // 인자가 없거나 `serve`이면 서버, 그 외에는 관리 명령 (cli.rs)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::Command::parse(&args) {
+        Ok(cli::Command::Serve) => {
+            // SAFETY: runtime을 만들기 전이라 이 프로세스에는 아직 main 스레드만 있습니다.
+            #[cfg(unix)]
+            let systemd = unsafe { axum_rest_api::listener::take_systemd_socket() };
+            #[cfg(not(unix))]
+            let systemd = None;
+            tokio::runtime::Runtime::new()?.block_on(serve(systemd))
+        }
+        Ok(command) => {
+            if let Err(e) = tokio::runtime::Runtime::new()?.block_on(cli::run(command)) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            std::process::exit(2);
+        }
+    }
+}
 
-    // 수정된 서비스 사용
-    axum::serve(listener, app_with_connect_info) 
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+async fn serve(systemd: Option<SystemdSocket>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = init_app().await?;
+    let app = build_router(config.clone());
+
+    let mut options = server::ServeOptions {
+        header_read_timeout: config.limits.header_read_timeout,
+        drain_timeout: config.shutdown.drain_timeout,
+        tls: None,
+    };
+
+    // TLS_CERT_PATH / TLS_KEY_PATH가 있으면 HTTPS, 인증서는 파일 변경이나 SIGHUP에 다시 읽습니다.
+    let mut redirect = None;
+    if let Some(tls_config) = config.tls.clone() {
+        let cert = std::sync::Arc::new(tls::ReloadableCert::load(tls_config.clone())?);
+        tls::spawn_reloader(cert.clone(), config.app_state.shutdown.clone());
+        options.tls = Some(tls::acceptor(cert)?);
+
+        if let Some(redirect_port) = tls_config.redirect_port {
+            let https_port = config.port.parse().map_err(|_| format!("PORT must be a port number, got '{}'", config.port))?;
+            let redirect_listener = tokio::net::TcpListener::bind(format!("{}:{}", config.host, redirect_port)).await?;
+            tracing::info!(addr = %redirect_listener.local_addr()?, "HTTP -> HTTPS 리다이렉트 중입니다");
+            redirect = Some((redirect_listener, tls::redirect_router(https_port)));
+        }
+    }
 
-    // 이 부분은 테스트용으로 유지
-    tokio::time::sleep(std::time::Duration::from_secs(2)).await; 
-    println!("서버가 성공적으로 종료되었습니다.");
+    // systemd 소켓 활성화(LISTEN_FDS) > Unix 소켓(LISTEN_UNIX_PATH) > HOST:PORT
+    let listener = Listener::bind(&config.host, &config.port, systemd).await?;
+    tracing::info!(addr = %listener, https = options.tls.is_some(), "서버가 실행 중입니다");
+
+    // 연결마다 ConnectInfo와 헤더 읽기 제한 시간을 적용하는 accept 루프
+    let shutdown = server::shutdown_signal(config.app_state.shutdown.clone(), config.shutdown.clone());
+    let redirect_server = async {
+        if let Some((redirect_listener, redirect_app)) = redirect {
+            let redirect_options = server::ServeOptions { tls: None, ..options.clone() };
+            let stop = config.app_state.shutdown.clone();
+            server::serve(redirect_listener, redirect_app, redirect_options, stop.cancelled_owned()).await;
+        }
+    };
+    let main_server = async {
+        match &listener {
+            Listener::Tcp(tcp) => server::serve(tcp, app, options.clone(), shutdown).await,
+            #[cfg(unix)]
+            Listener::Unix { listener: unix, .. } => server::serve(unix, app, options.clone(), shutdown).await,
+        }
+    };
+    tokio::join!(main_server, redirect_server);
+    listener.cleanup();
+
+    config.db_pool.close().await;
+    tracing::info!("서버가 성공적으로 종료되었습니다.");
+    telemetry::shutdown_tracing();
 
     Ok(())
 }
 
-async fn shutdown_signal() {
-    tokio::signal::ctrl_c()
-        .await
-        .expect("failed to install CTRL+C signal handler");
-    println!("SIGINT 신호 수신, 서버 종료 시작...");
-}
-
 // --------------------
 // > tree ./axum-rest-api -L 3 -a -I "target" -I ".git"
 // ./axum-rest-api
@@ -0,0 +1,191 @@
+// Prometheus 메트릭
+//
+// - http_requests_total / http_request_duration_seconds: method, route(매칭된 라우트 템플릿), status(2xx..5xx)
+//   라우트는 "/item/:id" 같은 템플릿으로 기록하므로 "/item/42" 처럼 값마다 라벨이 늘어나지 않습니다.
+// - db_pool_connections / db_pool_idle_connections: /metrics 요청 시점의 풀 상태
+// - db_query_duration_seconds / db_queries_total: `observe_query`로 감싼 쿼리
+//
+// METRICS_REQUIRE_ADMIN_KEY=true 이면 /metrics 도 관리자 인증을 거칩니다.
+pub mod slow;
+
+use axum::{
+    body::Body,
+    extract::{Extension, MatchedPath},
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use sqlx::{Any, Execute};
+use crate::db::{self, DbPool};
+use std::env;
+use std::future::Future;
+use std::sync::LazyLock;
+use std::time::Instant;
+use tracing::Instrument;
+
+#[derive(Clone, Debug, Default)]
+pub struct MetricsConfig {
+    /// /metrics 에 관리자 인증(관리자 키 또는 `admin` 스코프)을 요구할지 여부
+    pub require_admin_key: bool,
+}
+
+impl MetricsConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let require_admin_key = match env::var("METRICS_REQUIRE_ADMIN_KEY").as_deref() {
+            Ok("true") => true,
+            Ok("false") | Err(_) => false,
+            Ok(other) => return Err(format!("METRICS_REQUIRE_ADMIN_KEY must be true or false, got '{}'", other)),
+        };
+        Ok(MetricsConfig { require_admin_key })
+    }
+}
+
+pub struct Metrics {
+    pub registry: Registry,
+    pub http_requests_total: IntCounterVec,
+    pub http_request_duration_seconds: HistogramVec,
+    pub db_pool_connections: IntGauge,
+    pub db_pool_idle_connections: IntGauge,
+    pub db_queries_total: IntCounterVec,
+    pub db_query_duration_seconds: HistogramVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency in seconds"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let db_pool_connections =
+            IntGauge::new("db_pool_connections", "Number of open connections in the DB pool").unwrap();
+        let db_pool_idle_connections =
+            IntGauge::new("db_pool_idle_connections", "Number of idle connections in the DB pool").unwrap();
+        let db_queries_total = IntCounterVec::new(
+            Opts::new("db_queries_total", "Total number of DB queries"),
+            &["query", "outcome"],
+        )
+        .unwrap();
+        let db_query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "DB query latency in seconds")
+                .buckets(vec![0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5]),
+            &["query"],
+        )
+        .unwrap();
+
+        registry.register(Box::new(http_requests_total.clone())).unwrap();
+        registry.register(Box::new(http_request_duration_seconds.clone())).unwrap();
+        registry.register(Box::new(db_pool_connections.clone())).unwrap();
+        registry.register(Box::new(db_pool_idle_connections.clone())).unwrap();
+        registry.register(Box::new(db_queries_total.clone())).unwrap();
+        registry.register(Box::new(db_query_duration_seconds.clone())).unwrap();
+
+        Metrics {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            db_pool_connections,
+            db_pool_idle_connections,
+            db_queries_total,
+            db_query_duration_seconds,
+        }
+    }
+}
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+fn status_class(status: StatusCode) -> &'static str {
+    match status.as_u16() {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// 쿼리를 `db.query` span 안에서 실행하고, 실행 시간과 성공/실패 횟수를 기록합니다.
+/// 임계값(SLOW_QUERY_MS)을 넘으면 SQL 문(값은 가림)과 함께 느린 쿼리로 남깁니다.
+/// `name`은 라벨로 쓰이므로 고정된 짧은 이름을 사용합니다.
+///
+/// ```ignore
+/// observe_query("list_users", sqlx::query("SELECT ..."), |q| q.fetch_all(&db_pool)).await
+/// ```
+pub async fn observe_query<'q, Q, F, Fut, T, E>(name: &'static str, query: Q, run: F) -> Result<T, E>
+where
+    Q: Execute<'q, Any>,
+    F: FnOnce(Q) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let sql = slow::redact_sql(query.sql());
+    let span = tracing::info_span!(
+        "db.query",
+        otel.name = name,
+        otel.kind = "client",
+        otel.status_code = tracing::field::Empty,
+        db.system = db::system(),
+        db.operation.name = name,
+        db.query.text = %sql,
+    );
+    let start = Instant::now();
+    let result = run(query).instrument(span.clone()).await;
+    let elapsed = start.elapsed();
+    if result.is_err() {
+        span.record("otel.status_code", "ERROR");
+    }
+    slow::SLOW_EVENTS.record_query(name, &sql, elapsed);
+    METRICS
+        .db_query_duration_seconds
+        .with_label_values(&[name])
+        .observe(elapsed.as_secs_f64());
+    METRICS
+        .db_queries_total
+        .with_label_values(&[name, if result.is_ok() { "ok" } else { "error" }])
+        .inc();
+    result
+}
+
+pub async fn metrics_middleware(req: Request<Body>, next: Next) -> Response {
+    let start = Instant::now();
+    let method = req.method().to_string();
+    // 매칭되지 않은 요청(404)은 URI를 라벨로 쓰지 않고 하나로 묶습니다.
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(req).await;
+
+    let labels = [method.as_str(), route.as_str(), status_class(response.status())];
+    METRICS.http_requests_total.with_label_values(&labels).inc();
+    METRICS
+        .http_request_duration_seconds
+        .with_label_values(&labels)
+        .observe(start.elapsed().as_secs_f64());
+
+    response
+}
+
+/// Prometheus text exposition format
+pub async fn metrics_handler(Extension(db_pool): Extension<DbPool>) -> impl IntoResponse {
+    METRICS.db_pool_connections.set(db_pool.size() as i64);
+    METRICS.db_pool_idle_connections.set(db_pool.num_idle() as i64);
+
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    match encoder.encode(&METRICS.registry.gather(), &mut buffer) {
+        Ok(()) => ([(header::CONTENT_TYPE, encoder.format_type().to_string())], buffer).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
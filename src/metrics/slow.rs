@@ -0,0 +1,138 @@
+// 느린 쿼리 / 느린 요청 감지
+//
+// 임계값을 넘은 쿼리와 요청을 warn 로그로 남기고, 최근 이벤트를 메모리 링 버퍼에 보관합니다.
+// 보관한 이벤트는 GET /admin/slow-events 로 조회합니다.
+//
+// 환경 변수:
+//   SLOW_QUERY_MS=200       (기본값 200)
+//   SLOW_REQUEST_MS=1000    (기본값 1000)
+//   SLOW_EVENTS_CAPACITY=100
+use std::collections::VecDeque;
+use std::env;
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use crate::auth::now_epoch;
+use crate::middleware::RequestId;
+use crate::models::{SlowEvent, SlowEventKind};
+
+pub struct SlowEventLog {
+    pub query_threshold: Duration,
+    pub request_threshold: Duration,
+    capacity: usize,
+    events: Mutex<VecDeque<SlowEvent>>,
+}
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .map(|v| v.parse().unwrap_or_else(|_| panic!("{} must be a number", name)))
+        .unwrap_or(default)
+}
+
+impl SlowEventLog {
+    pub fn from_env() -> Self {
+        let capacity = env_u64("SLOW_EVENTS_CAPACITY", 100) as usize;
+        SlowEventLog {
+            query_threshold: Duration::from_millis(env_u64("SLOW_QUERY_MS", 200)),
+            request_threshold: Duration::from_millis(env_u64("SLOW_REQUEST_MS", 1000)),
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn push(&self, event: SlowEvent) {
+        let mut events = self.events.lock().unwrap();
+        if events.len() >= self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// 임계값을 넘었으면 기록합니다. `sql`은 이미 `redact_sql`을 거친 문자열이어야 합니다.
+    pub fn record_query(&self, name: &str, sql: &str, elapsed: Duration) {
+        if elapsed < self.query_threshold || self.capacity == 0 {
+            return;
+        }
+        let duration_ms = elapsed.as_millis() as u64;
+        tracing::warn!(query = name, sql, duration_ms, "slow query");
+        self.push(SlowEvent {
+            kind: SlowEventKind::Query,
+            name: name.to_string(),
+            sql: Some(sql.to_string()),
+            duration_ms,
+            at: now_epoch(),
+            request_id: RequestId::current().map(|id| id.0),
+        });
+    }
+
+    pub fn record_request(&self, method: &str, route: &str, elapsed: Duration) {
+        if elapsed < self.request_threshold || self.capacity == 0 {
+            return;
+        }
+        let duration_ms = elapsed.as_millis() as u64;
+        tracing::warn!(method, route, duration_ms, "slow request");
+        self.push(SlowEvent {
+            kind: SlowEventKind::Request,
+            name: format!("{} {}", method, route),
+            sql: None,
+            duration_ms,
+            at: now_epoch(),
+            request_id: RequestId::current().map(|id| id.0),
+        });
+    }
+
+    /// 최근 이벤트부터 최대 `limit`개
+    pub fn recent(&self, limit: usize) -> Vec<SlowEvent> {
+        self.events.lock().unwrap().iter().rev().take(limit).cloned().collect()
+    }
+}
+
+pub static SLOW_EVENTS: LazyLock<SlowEventLog> = LazyLock::new(SlowEventLog::from_env);
+
+/// SQL 문자열에서 리터럴 값(문자열, 숫자)을 `?`로 바꾸고 공백을 정리합니다.
+/// bind 파라미터는 원래 `?`로만 남아 있으므로, SQL에 직접 적힌 값까지 로그에 남지 않게 합니다.
+pub fn redact_sql(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut prev_is_word = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                // 닫는 따옴표까지 건너뜀 ('' 처럼 두 번 쓴 따옴표는 이스케이프)
+                while let Some(n) = chars.next() {
+                    if n == '\\' {
+                        chars.next();
+                    } else if n == c {
+                        if chars.peek() == Some(&c) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                out.push('?');
+                prev_is_word = false;
+            }
+            '0'..='9' if !prev_is_word => {
+                while chars.peek().is_some_and(|n| n.is_ascii_alphanumeric() || *n == '.') {
+                    chars.next();
+                }
+                out.push('?');
+                prev_is_word = false;
+            }
+            c if c.is_whitespace() => {
+                if !out.ends_with(' ') && !out.is_empty() {
+                    out.push(' ');
+                }
+                prev_is_word = false;
+            }
+            c => {
+                out.push(c);
+                prev_is_word = c.is_alphanumeric() || c == '_';
+            }
+        }
+    }
+
+    out.trim_end().to_string()
+}
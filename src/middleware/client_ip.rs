@@ -0,0 +1,209 @@
+// 클라이언트 IP 결정 (trusted proxy 처리)
+//
+// 접속한 peer가 신뢰하는 프록시(TRUSTED_PROXIES)일 때만 전달 헤더를 봅니다.
+//  - 프록시가 채우는 헤더 하나(TRUSTED_PROXY_HEADER)만 읽습니다. 다른 헤더는 클라이언트가 보낸 그대로
+//    전달될 수 있으므로, 설정한 헤더가 없어도 다른 헤더로 넘어가지 않습니다.
+//  - 목록을 오른쪽(가장 가까운 hop)부터 읽으면서 신뢰하는 프록시는 건너뛰고,
+//    처음 만나는 신뢰하지 않는 주소를 클라이언트 IP로 봅니다.
+//  - 신뢰하지 않는 peer가 보낸 헤더는 무시합니다. (IP 위조 방지)
+//
+// 환경 변수 예:
+//   TRUSTED_PROXIES=127.0.0.1/32,10.0.0.0/8,::1
+//   TRUSTED_PROXY_HEADER=x-forwarded-for   # 또는 forwarded (RFC 7239)
+use axum::{
+    async_trait,
+    body::Body,
+    extract::{ConnectInfo, FromRequestParts, State},
+    http::{request::Parts, HeaderMap, Request, StatusCode},
+    middleware::Next,
+    response::IntoResponse,
+};
+use ipnet::IpNet;
+use std::env;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use crate::AppState;
+
+/// 신뢰하는 프록시가 클라이언트 주소를 전달하는 헤더
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProxyHeader {
+    #[default]
+    XForwardedFor,
+    /// RFC 7239 `Forwarded: for=...`
+    Forwarded,
+}
+
+impl ProxyHeader {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "x-forwarded-for" => Some(ProxyHeader::XForwardedFor),
+            "forwarded" => Some(ProxyHeader::Forwarded),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies {
+    nets: Vec<IpNet>,
+    header: ProxyHeader,
+}
+
+impl TrustedProxies {
+    /// "10.0.0.0/8" 같은 CIDR 또는 단일 IP 목록
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let nets = list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse::<IpNet>()
+                    .or_else(|_| s.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|_| format!("invalid trusted proxy '{}'", s))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TrustedProxies { nets, header: ProxyHeader::default() })
+    }
+
+    pub fn with_header(self, header: ProxyHeader) -> Self {
+        TrustedProxies { header, ..self }
+    }
+
+    pub fn from_env() -> Result<Self, String> {
+        let header = match env::var("TRUSTED_PROXY_HEADER") {
+            Ok(v) => ProxyHeader::parse(&v)
+                .ok_or_else(|| format!("TRUSTED_PROXY_HEADER must be x-forwarded-for or forwarded, got '{}'", v))?,
+            Err(_) => ProxyHeader::default(),
+        };
+        Ok(TrustedProxies::parse(&env::var("TRUSTED_PROXIES").unwrap_or_default())
+            .map_err(|e| format!("TRUSTED_PROXIES: {}", e))?
+            .with_header(header))
+    }
+
+    pub fn is_trusted(&self, ip: &IpAddr) -> bool {
+        let ip = canonical(*ip);
+        self.nets.iter().any(|net| net.contains(&ip))
+    }
+
+    /// peer 주소와 요청 헤더로 실제 클라이언트 IP를 결정합니다.
+    pub fn resolve(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.is_trusted(&peer) {
+            return canonical(peer);
+        }
+
+        let hops = match self.header {
+            ProxyHeader::XForwardedFor => x_forwarded_for(headers),
+            ProxyHeader::Forwarded => forwarded_for(headers),
+        };
+
+        let mut client = canonical(peer);
+        for hop in hops.iter().rev() {
+            match hop {
+                Some(ip) => {
+                    client = canonical(*ip);
+                    if !self.is_trusted(&client) {
+                        break;
+                    }
+                }
+                // "unknown"이나 난독화된 식별자는 더 이상 따라갈 수 없으므로
+                // 마지막으로 확인한 hop을 클라이언트로 봅니다.
+                None => break,
+            }
+        }
+        client
+    }
+}
+
+// IPv4-mapped IPv6 (::ffff:1.2.3.4)는 IPv4로 취급합니다.
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+        v4 => v4,
+    }
+}
+
+/// "192.0.2.43", "192.0.2.43:4711", "[2001:db8::1]", "[2001:db8::1]:4711", "2001:db8::1"
+fn parse_node(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+    value
+        .parse::<IpAddr>()
+        .ok()
+        .or_else(|| value.parse::<SocketAddr>().ok().map(|s| s.ip()))
+}
+
+/// X-Forwarded-For: 여러 줄의 헤더는 순서대로 이어 붙인 하나의 목록으로 봅니다.
+/// 주소로 읽을 수 없는 hop은 `None`입니다.
+pub fn x_forwarded_for(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter(|s| !s.trim().is_empty())
+        .map(parse_node)
+        .collect()
+}
+
+/// RFC 7239 Forwarded: `for=192.0.2.60;proto=http;by=203.0.113.43, for="[2001:db8:cafe::17]:4711"`
+/// 요소 하나가 hop 하나입니다. `for=`가 없는 요소도 hop 수에 넣고 `None`으로 둡니다.
+pub fn forwarded_for(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    headers
+        .get_all("forwarded")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .filter(|element| !element.trim().is_empty())
+        .map(|element| {
+            element.split(';').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                key.trim().eq_ignore_ascii_case("for").then(|| parse_node(value))
+            })?
+        })
+        .collect()
+}
+
+/// 결정된 클라이언트 IP. `client_ip_middleware`가 request extensions에 넣어 두며,
+/// 로깅, 요청 제한, 감사 로그 등에서 같은 값을 추출기로 꺼내 씁니다.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+impl fmt::Display for ClientIp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(ip) = parts.extensions.get::<ClientIp>() {
+            return Ok(*ip);
+        }
+        // client_ip_middleware가 없는 라우터에서는 peer 주소를 그대로 사용합니다.
+        parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| ClientIp(canonical(addr.ip())))
+            .ok_or((StatusCode::INTERNAL_SERVER_ERROR, "client address is not available"))
+    }
+}
+
+pub async fn client_ip_middleware(
+    State(app_state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    mut req: Request<Body>,
+    next: Next,
+) -> impl IntoResponse {
+    let ip = app_state.trusted_proxies.resolve(addr.ip(), req.headers());
+    req.extensions_mut().insert(ClientIp(ip));
+    next.run(req).await
+}
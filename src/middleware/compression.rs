@@ -0,0 +1,78 @@
+// 응답 압축 / 요청 본문 압축 해제
+//
+// - 응답: Accept-Encoding에 따라 gzip, br, zstd 중 하나로 압축합니다.
+//   COMPRESSION_MIN_SIZE보다 작은 응답, 이미지, SSE(text/event-stream)는 압축하지 않습니다.
+// - 요청: `Content-Encoding: gzip` 본문을 풀어서 핸들러에 넘깁니다. 지원하지 않는 인코딩은 415
+//   본문 크기 제한(`limits_middleware`)은 압축을 푼 크기에 적용되므로 압축 폭탄도 막힙니다.
+//   REQUEST_DECOMPRESSION=false이면 어떤 본문도 풀거나 거부하지 않고 그대로 넘깁니다.
+//
+// 환경 변수 예:
+//   COMPRESSION_ENABLED=true
+//   COMPRESSION_ALGORITHMS=gzip,br,zstd
+//   COMPRESSION_MIN_SIZE=1024     # 바이트, 최대 65535
+//   REQUEST_DECOMPRESSION=true
+use std::env;
+use tower_http::compression::{
+    predicate::{NotForContentType, Predicate, SizeAbove},
+    CompressionLayer,
+};
+use tower_http::decompression::RequestDecompressionLayer;
+
+#[derive(Clone, Debug)]
+pub struct CompressionConfig {
+    pub gzip: bool,
+    pub br: bool,
+    pub zstd: bool,
+    pub min_size: u16,
+    pub request_decompression: bool,
+}
+
+impl CompressionConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let enabled = env::var("COMPRESSION_ENABLED").map(|v| v == "true").unwrap_or(true);
+        let algorithms: Vec<String> = env::var("COMPRESSION_ALGORITHMS")
+            .unwrap_or_else(|_| "gzip,br,zstd".to_string())
+            .split(',')
+            .map(|s| s.trim().to_ascii_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        for algorithm in &algorithms {
+            if !["gzip", "br", "zstd"].contains(&algorithm.as_str()) {
+                return Err(format!("unknown compression algorithm '{}'", algorithm));
+            }
+        }
+        let has = |name: &str| enabled && algorithms.iter().any(|a| a == name);
+
+        Ok(CompressionConfig {
+            gzip: has("gzip"),
+            br: has("br"),
+            zstd: has("zstd"),
+            min_size: env::var("COMPRESSION_MIN_SIZE")
+                .ok()
+                .map(|v| v.parse().map_err(|_| format!("COMPRESSION_MIN_SIZE must be a number up to 65535, got '{}'", v)))
+                .transpose()?
+                .unwrap_or(1024),
+            request_decompression: env::var("REQUEST_DECOMPRESSION").map(|v| v == "true").unwrap_or(true),
+        })
+    }
+
+    pub fn layer(&self) -> CompressionLayer<impl Predicate + use<>> {
+        let predicate = SizeAbove::new(self.min_size)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE);
+
+        CompressionLayer::new()
+            .gzip(self.gzip)
+            .br(self.br)
+            .zstd(self.zstd)
+            .compress_when(predicate)
+    }
+
+    /// 꺼져 있으면 아무 인코딩도 풀지 않고 모든 본문을 통과시키는 (아무 일도 하지 않는) layer
+    pub fn decompression_layer(&self) -> RequestDecompressionLayer {
+        RequestDecompressionLayer::new()
+            .gzip(self.request_decompression)
+            .pass_through_unaccepted(!self.request_decompression)
+    }
+}
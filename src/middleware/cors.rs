@@ -0,0 +1,166 @@
+// CORS 정책 (환경별 설정)
+//
+// APP_ENV=production(기본값)에서는 CORS_ALLOWED_ORIGINS에 적은 origin만 허용합니다. 설정이 없으면 아무 origin도 허용하지 않습니다.
+// APP_ENV=development에서 CORS_ALLOWED_ORIGINS가 없으면 http://localhost:*, http://127.0.0.1:* 를 허용합니다.
+//
+// 환경 변수 예:
+//   CORS_ALLOWED_ORIGINS=https://app.example.com,https://*.example.com
+//   CORS_ALLOWED_METHODS=GET,POST,DELETE
+//   CORS_ALLOWED_HEADERS=content-type,authorization,x-admin-api-key,x-request-id
+//   CORS_ALLOW_CREDENTIALS=true
+//   CORS_MAX_AGE=600
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use std::env;
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use super::REQUEST_ID_HEADER;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OriginPattern {
+    /// 모든 origin ("*"). credentials와 함께 쓸 수 없습니다.
+    Any,
+    /// "https://app.example.com"
+    Exact(String),
+    /// "https://*.example.com" -> scheme "https://", suffix ".example.com"
+    /// 하위 도메인만 매칭하며 example.com 자체는 매칭하지 않습니다.
+    WildcardSubdomain { scheme: String, suffix: String },
+    /// "http://localhost:*" -> 포트와 관계없이 매칭
+    AnyPort(String),
+}
+
+impl OriginPattern {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim().trim_end_matches('/').to_ascii_lowercase();
+        if s == "*" {
+            return Ok(OriginPattern::Any);
+        }
+        let Some(scheme_end) = s.find("://").map(|i| i + 3) else {
+            return Err(format!("CORS origin '{}' must include a scheme", s));
+        };
+        let (scheme, host) = s.split_at(scheme_end);
+        if let Some(suffix) = host.strip_prefix('*') {
+            if !suffix.starts_with('.') || suffix.len() < 2 {
+                return Err(format!("invalid wildcard CORS origin '{}'", s));
+            }
+            return Ok(OriginPattern::WildcardSubdomain {
+                scheme: scheme.to_string(),
+                suffix: suffix.to_string(),
+            });
+        }
+        if let Some(base) = s.strip_suffix(":*") {
+            return Ok(OriginPattern::AnyPort(base.to_string()));
+        }
+        Ok(OriginPattern::Exact(s))
+    }
+
+    pub fn matches(&self, origin: &str) -> bool {
+        let origin = origin.to_ascii_lowercase();
+        match self {
+            OriginPattern::Any => true,
+            OriginPattern::Exact(exact) => origin == *exact,
+            OriginPattern::WildcardSubdomain { scheme, suffix } => origin
+                .strip_prefix(scheme.as_str())
+                .is_some_and(|host| {
+                    host.strip_suffix(suffix.as_str())
+                        .is_some_and(|sub| !sub.is_empty() && !sub.contains('/'))
+                }),
+            OriginPattern::AnyPort(base) => origin == *base
+                || origin
+                    .strip_prefix(base.as_str())
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .is_some_and(|port| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit())),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<OriginPattern>,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    pub allow_credentials: bool,
+    pub max_age: Duration,
+}
+
+fn split_list(s: &str) -> impl Iterator<Item = &str> {
+    s.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+impl CorsConfig {
+    pub fn from_env() -> Result<Self, String> {
+        let production = env::var("APP_ENV").map(|v| v != "development").unwrap_or(true);
+
+        let allowed_origins = match env::var("CORS_ALLOWED_ORIGINS") {
+            Ok(origins) => split_list(&origins)
+                .map(OriginPattern::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("CORS_ALLOWED_ORIGINS: {}", e))?,
+            Err(_) if production => Vec::new(),
+            Err(_) => vec![
+                OriginPattern::AnyPort("http://localhost".to_string()),
+                OriginPattern::AnyPort("http://127.0.0.1".to_string()),
+            ],
+        };
+
+        let allowed_methods = split_list(
+            &env::var("CORS_ALLOWED_METHODS").unwrap_or_else(|_| "GET,POST,DELETE".to_string()),
+        )
+        .map(|m| m.to_ascii_uppercase().parse::<Method>().map_err(|_| format!("invalid CORS method '{}'", m)))
+        .collect::<Result<_, _>>()?;
+
+        let allowed_headers = split_list(
+            &env::var("CORS_ALLOWED_HEADERS")
+                .unwrap_or_else(|_| "content-type,authorization,x-admin-api-key,x-request-id".to_string()),
+        )
+        .map(|h| h.parse::<HeaderName>().map_err(|_| format!("invalid CORS header '{}'", h)))
+        .collect::<Result<_, _>>()?;
+
+        let allow_credentials = env::var("CORS_ALLOW_CREDENTIALS").map(|v| v == "true").unwrap_or(false);
+        if allow_credentials && allowed_origins.contains(&OriginPattern::Any) {
+            return Err("CORS_ALLOW_CREDENTIALS=true cannot be combined with CORS_ALLOWED_ORIGINS=*".to_string());
+        }
+
+        let max_age = env::var("CORS_MAX_AGE")
+            .ok()
+            .map(|v| v.parse::<u64>().map_err(|_| format!("CORS_MAX_AGE must be seconds, got '{}'", v)))
+            .transpose()?
+            .unwrap_or(600);
+
+        Ok(CorsConfig {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers,
+            allow_credentials,
+            max_age: Duration::from_secs(max_age),
+        })
+    }
+
+    /// 라우터에 붙일 `CorsLayer`를 만듭니다. preflight(OPTIONS) 요청은 이 레이어가 바로 응답합니다.
+    pub fn layer(&self) -> CorsLayer {
+        let patterns = self.allowed_origins.clone();
+        let allow_origin = if patterns == [OriginPattern::Any] {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+                origin
+                    .to_str()
+                    .map(|origin| patterns.iter().any(|p| p.matches(origin)))
+                    .unwrap_or(false)
+            })
+        };
+
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(self.allowed_methods.clone())
+            .allow_headers(self.allowed_headers.clone())
+            .allow_credentials(self.allow_credentials)
+            .max_age(self.max_age)
+            .expose_headers([
+                HeaderName::from_static("ratelimit-limit"),
+                HeaderName::from_static("ratelimit-remaining"),
+                HeaderName::from_static("ratelimit-reset"),
+                header::RETRY_AFTER,
+                REQUEST_ID_HEADER,
+            ])
+    }
+}
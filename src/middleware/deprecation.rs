@@ -0,0 +1,90 @@
+// 버전 없는 예전 경로(legacy)의 폐기 안내 헤더
+//
+// /api/v1 이전의 RPC 스타일 경로는 그대로 동작하지만, 응답에 다음 헤더를 붙입니다.
+//   Deprecation: @<unix epoch>                           (RFC 9745)
+//   Sunset: <HTTP-date>                                  (RFC 8594)
+//   Link: </api/v1/users/42>; rel="successor-version"
+//
+// 환경 변수 예 (HTTP-date 형식):
+//   LEGACY_API_DEPRECATED_AT="Mon, 19 Oct 2026 00:00:00 GMT"
+//   LEGACY_API_SUNSET="Mon, 19 Apr 2027 00:00:00 GMT"
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::env;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// (예전 경로, 대체 경로). 경로 변수 이름이 같아야 값을 옮겨 줄 수 있습니다.
+pub const LEGACY_ROUTES: &[(&str, &str)] = &[
+    ("/create-user", "/api/v1/users"),
+    ("/create-user-db", "/api/v1/users"),
+    ("/users", "/api/v1/users"),
+    ("/axum-users", "/api/v1/users"),
+    ("/delete-user/:id", "/api/v1/users/:id"),
+    ("/item/:id", "/api/v1/items/:id"),
+    ("/add-item", "/api/v1/items"),
+    ("/users/:id/tokens", "/api/v1/users/:id/tokens"),
+    ("/users/:id/tokens/:token_id", "/api/v1/users/:id/tokens/:token_id"),
+];
+
+const DEFAULT_DEPRECATED_AT: &str = "Mon, 19 Oct 2026 00:00:00 GMT";
+const DEFAULT_SUNSET: &str = "Mon, 19 Apr 2027 00:00:00 GMT";
+
+#[derive(Clone, Debug)]
+pub struct DeprecationConfig {
+    pub deprecated_at: SystemTime,
+    pub sunset: SystemTime,
+}
+
+fn env_http_date(name: &str, default: &str) -> Result<SystemTime, String> {
+    let value = env::var(name).unwrap_or_else(|_| default.to_string());
+    httpdate::parse_http_date(&value).map_err(|_| format!("{} must be an HTTP-date like \"{}\"", name, default))
+}
+
+impl DeprecationConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Ok(DeprecationConfig {
+            deprecated_at: env_http_date("LEGACY_API_DEPRECATED_AT", DEFAULT_DEPRECATED_AT)?,
+            sunset: env_http_date("LEGACY_API_SUNSET", DEFAULT_SUNSET)?,
+        })
+    }
+}
+
+/// 예전 경로 템플릿(`/delete-user/:id`)과 실제 경로(`/delete-user/42`)로 대체 경로(`/api/v1/users/42`)를 만듭니다.
+pub fn successor_path(template: &str, path: &str) -> Option<String> {
+    let (_, successor) = LEGACY_ROUTES.iter().find(|(legacy, _)| *legacy == template)?;
+    let mut successor = successor.to_string();
+    for (segment, value) in template.split('/').zip(path.split('/')) {
+        if let Some(name) = segment.strip_prefix(':') {
+            successor = successor.replace(&format!(":{}", name), value);
+        }
+    }
+    Some(successor)
+}
+
+/// `LEGACY_ROUTES`의 경로에만 붙입니다 (`route_layer`).
+pub async fn deprecation_middleware(
+    State(config): State<Arc<DeprecationConfig>>,
+    matched_path: Option<MatchedPath>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let successor = matched_path.and_then(|m| successor_path(m.as_str(), req.uri().path()));
+    let mut res = next.run(req).await;
+
+    let deprecated_at = config.deprecated_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let headers = res.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_str(&format!("@{}", deprecated_at)).unwrap());
+    headers.insert("sunset", HeaderValue::from_str(&httpdate::fmt_http_date(config.sunset)).unwrap());
+    if let Some(successor) = successor
+        && let Ok(link) = HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor))
+    {
+        headers.insert("link", link);
+    }
+    res
+}
@@ -0,0 +1,152 @@
+// 요청 본문 크기 제한 / 요청 처리 시간 제한
+//
+// - 본문 크기: 기본값(BODY_LIMIT_BYTES)과 라우트별 값(BODY_LIMIT_ROUTES). 넘으면 413
+// - 처리 시간: 기본값(REQUEST_TIMEOUT_SECS)과 라우트별 값(REQUEST_TIMEOUT_ROUTES). 0이면 제한 없음
+//   본문을 다 받기 전에 시간이 지나면 408(클라이언트가 느림), 핸들러 처리 중이면 503
+// - 헤더 읽기 시간(HEADER_READ_TIMEOUT_SECS)은 연결 단위 설정이라 `server::serve`에서 적용합니다.
+//
+// 환경 변수 예:
+//   BODY_LIMIT_BYTES=1048576
+//   BODY_LIMIT_ROUTES=/add-item=16384,/create-user-db=16384   # 매칭된 라우트 템플릿 기준
+//   REQUEST_TIMEOUT_SECS=30
+//   REQUEST_TIMEOUT_ROUTES=/axum-users=10
+//   HEADER_READ_TIMEOUT_SECS=10
+use axum::{
+    body::{to_bytes, Body},
+    extract::{MatchedPath, State},
+    http::{header, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+use crate::AppError;
+
+#[derive(Clone, Debug)]
+pub struct LimitsConfig {
+    pub body_limit: usize,
+    pub route_body_limits: HashMap<String, usize>,
+    /// `None`이면 제한 없음
+    pub request_timeout: Option<Duration>,
+    pub route_timeouts: HashMap<String, Option<Duration>>,
+    pub header_read_timeout: Duration,
+}
+
+fn parse_route_map<T>(var: &str, parse: impl Fn(&str) -> Option<T>) -> Result<HashMap<String, T>, String> {
+    env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|entry| {
+            let (route, value) = entry.trim().rsplit_once('=')?;
+            Some(match parse(value.trim()) {
+                Some(value) => Ok((route.trim().to_string(), value)),
+                None => Err(format!("invalid {} entry '{}'", var, entry)),
+            })
+        })
+        .collect()
+}
+
+/// 설정되어 있으면 `parse`로 읽고, 읽지 못하면 "`name` must be a number" 오류
+fn env_number<T>(name: &str, parse: impl Fn(&str) -> Option<T>) -> Result<Option<T>, String> {
+    env::var(name)
+        .ok()
+        .map(|v| parse(&v).ok_or_else(|| format!("{} must be a number, got '{}'", name, v)))
+        .transpose()
+}
+
+fn parse_timeout(s: &str) -> Option<Option<Duration>> {
+    let secs: u64 = s.parse().ok()?;
+    Some((secs > 0).then(|| Duration::from_secs(secs)))
+}
+
+impl LimitsConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Ok(LimitsConfig {
+            body_limit: env_number("BODY_LIMIT_BYTES", |v| v.parse().ok())?.unwrap_or(1024 * 1024),
+            route_body_limits: parse_route_map("BODY_LIMIT_ROUTES", |v| v.parse().ok())?,
+            request_timeout: env_number("REQUEST_TIMEOUT_SECS", parse_timeout)?.unwrap_or(Some(Duration::from_secs(30))),
+            route_timeouts: parse_route_map("REQUEST_TIMEOUT_ROUTES", parse_timeout)?,
+            header_read_timeout: Duration::from_secs(
+                env_number("HEADER_READ_TIMEOUT_SECS", |v| v.parse().ok())?.unwrap_or(10),
+            ),
+        })
+    }
+
+    pub fn body_limit_for(&self, route: &str) -> usize {
+        self.route_body_limits.get(route).copied().unwrap_or(self.body_limit)
+    }
+
+    pub fn timeout_for(&self, route: &str) -> Option<Duration> {
+        self.route_timeouts.get(route).copied().unwrap_or(self.request_timeout)
+    }
+}
+
+/// 본문을 제한 크기까지만 읽어 두고, 남은 시간 안에 핸들러를 실행합니다.
+/// 라우터에 `DefaultBodyLimit::disable()`을 함께 걸어 axum 기본 제한(2MB) 대신 이 설정을 따르게 합니다.
+pub async fn limits_middleware(
+    State(limits): State<Arc<LimitsConfig>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_default();
+    let body_limit = limits.body_limit_for(&route);
+    // 제한이 없으면 아주 먼 시각을 마감으로 둡니다.
+    let deadline = limits
+        .timeout_for(&route)
+        .map(|t| Instant::now() + t)
+        .unwrap_or_else(|| Instant::now() + Duration::from_secs(86400 * 365));
+
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if content_length.is_some_and(|len| len > body_limit) {
+        return payload_too_large(body_limit);
+    }
+
+    let (parts, body) = req.into_parts();
+    let bytes = match timeout_at(deadline, to_bytes(body, body_limit)).await {
+        Ok(Ok(bytes)) => bytes,
+        // to_bytes는 제한을 넘으면 LengthLimitError를 돌려줍니다.
+        Ok(Err(e)) if is_length_limit_error(&e) => return payload_too_large(body_limit),
+        Ok(Err(e)) => {
+            return AppError::InvalidInput(0, format!("Failed to read request body - {}", e)).into_response();
+        }
+        Err(_) => {
+            return AppError::RequestTimeout("timed out while reading the request body".to_string())
+                .into_response();
+        }
+    };
+    let req = Request::from_parts(parts, Body::from(bytes));
+
+    match timeout_at(deadline, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => {
+            tracing::warn!(route, "request timed out");
+            AppError::ServiceUnavailable("request timed out".to_string()).into_response()
+        }
+    }
+}
+
+fn payload_too_large(limit: usize) -> Response {
+    AppError::PayloadTooLarge(format!("request body exceeds {} bytes", limit)).into_response()
+}
+
+fn is_length_limit_error(e: &axum::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(e);
+    while let Some(err) = source {
+        if err.is::<http_body_util::LengthLimitError>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
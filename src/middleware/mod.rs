@@ -1,59 +1,192 @@
+pub mod client_ip;
+pub mod compression;
+pub mod cors;
+pub mod deprecation;
+pub mod limits;
+pub mod rate_limit;
+pub mod request_id;
+
+pub use client_ip::*;
+pub use compression::*;
+pub use cors::*;
+pub use deprecation::*;
+pub use limits::*;
+pub use rate_limit::*;
+pub use request_id::*;
+
 use axum::{
     body::Body,
-    extract::{ConnectInfo, State},
-    http::{Request, StatusCode},
+    extract::{MatchedPath, State},
+    http::{header, Request},
     middleware::Next,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
-use std::net::SocketAddr;
 use std::time::Instant;
 use std::sync::Arc;
-use crate::AppState;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use crate::db::DbPool;
+use crate::auth::{self, Principal, SCOPE_ADMIN, SCOPE_USERS_READ, SCOPE_USERS_WRITE};
+use crate::metrics::slow::SLOW_EVENTS;
+use crate::tls::ClientCert;
+use crate::{telemetry, AppError, AppState};
 
 
+/// `X-Admin-API-Key` 헤더(관리자 키) 또는 `Authorization: Bearer <token>`(개인 액세스 토큰)으로 인증합니다.
+/// 인증에 성공하면 `Principal`을 request extensions에 넣어 핸들러에서 사용할 수 있게 합니다.
+/// ADMIN_REQUIRE_CLIENT_CERT=true이면 관리자 키는 허용된 클라이언트 인증서(mTLS)와 함께 와야 합니다.
 pub async fn auth_middleware(
     State(app_state): State<Arc<AppState>>,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> impl IntoResponse {
-    let auth_header = req.headers()
+    let admin_key = req.headers()
         .get("X-Admin-API-Key")
         .and_then(|header| header.to_str().ok());
 
-    match auth_header {
+    let client_cert = req.extensions().get::<ClientCert>().cloned();
+    if let Some(cert) = &client_cert {
+        tracing::Span::current().record("client_cert", cert.subject.as_str());
+    }
+
+    let principal = match admin_key {
         Some(key) if key == app_state.admin_api_key => {
+            if let Err(reason) = app_state.admin_client_cert.check_admin(client_cert.as_ref()) {
+                return AppError::Unauthorized(reason).into_response();
+            }
+            Some(Principal::Admin)
+        }
+        Some(_) => None,
+        None => {
+            let bearer = req.headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|header| header.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .map(str::trim);
+
+            match (bearer, req.extensions().get::<DbPool>()) {
+                (Some(token), Some(db_pool)) => match auth::authenticate_token(db_pool, token).await {
+                    Ok(principal) => principal,
+                    Err(e) => {
+                        return AppError::InternalServerError(format!(
+                            "Failed to verify token - {}",
+                            e
+                        ))
+                        .into_response();
+                    }
+                },
+                _ => None,
+            }
+        }
+    };
+
+    match principal {
+        Some(principal) => {
+            let user = match &principal {
+                Principal::Admin => "admin".to_string(),
+                Principal::User { user_id, .. } => format!("user:{}", user_id),
+            };
+            tracing::Span::current().record("user", user);
+            req.extensions_mut().insert(principal);
             next.run(req).await
         },
-        _ => {
-            (StatusCode::UNAUTHORIZED, "Invalid API Key").into_response()
+        None => {
+            AppError::Unauthorized("Invalid API Key".to_string()).into_response()
         }
     }
 }
 
+/// `/admin` 라우트용. `auth_middleware` 뒤에서 실행되며 `admin` 스코프가 없으면 403을 반환합니다.
+/// `admin` 스코프 토큰도 관리자 키와 같은 클라이언트 인증서 정책을 따릅니다.
+pub async fn require_admin_middleware(
+    State(app_state): State<Arc<AppState>>,
+    req: Request<Body>,
+    next: Next,
+) -> impl IntoResponse {
+    match req.extensions().get::<Principal>() {
+        Some(principal) if principal.has_scope(SCOPE_ADMIN) => {
+            match app_state.admin_client_cert.check_admin(req.extensions().get::<ClientCert>()) {
+                Ok(()) => next.run(req).await,
+                Err(reason) => AppError::Forbidden(reason).into_response(),
+            }
+        }
+        Some(_) => AppError::Forbidden("admin scope required".to_string()).into_response(),
+        None => AppError::Unauthorized("Invalid API Key".to_string()).into_response(),
+    }
+}
+
+/// 사용자 변경 알림(SSE) 라우트용. `auth_middleware` 뒤에서 실행되며 `users:read` 스코프가 없으면 403을 반환합니다.
+pub async fn require_users_read_middleware(req: Request<Body>, next: Next) -> Response {
+    require_scope(SCOPE_USERS_READ, req, next).await
+}
+
+/// /api/v1 사용자 생성/수정/삭제 라우트용. `auth_middleware` 뒤에서 실행되며 `users:write` 스코프가 없으면 403을 반환합니다.
+pub async fn require_users_write_middleware(req: Request<Body>, next: Next) -> Response {
+    require_scope(SCOPE_USERS_WRITE, req, next).await
+}
+
+async fn require_scope(scope: &str, req: Request<Body>, next: Next) -> Response {
+    match req.extensions().get::<Principal>() {
+        Some(principal) if principal.has_scope(scope) => next.run(req).await,
+        Some(_) => AppError::Forbidden(format!("'{}' scope required", scope)).into_response(),
+        None => AppError::Unauthorized("Invalid API Key".to_string()).into_response(),
+    }
+}
+
+/// 요청마다 `http.request` span을 만들고, 응답 후 상태 코드와 처리 시간을 기록합니다.
+/// `user` 필드는 인증에 성공하면 `auth_middleware`가 채웁니다.
 pub async fn logging_middleware(
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    client_ip: ClientIp,
+    request_id: RequestId,
     req: Request<Body>,
     next: Next,
 ) -> impl IntoResponse {
     let start = Instant::now();
 
-    let client_ip = req
-        .headers()
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-        .map(String::from)
-        .unwrap_or_else(|| addr.ip().to_string());
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_default();
 
     let method = req.method().clone();
-    let uri = req.uri().clone();
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", req.method(), route),
+        otel.kind = "server",
+        otel.status_code = tracing::field::Empty,
+        request_id = %request_id,
+        method = %req.method(),
+        route = %route,
+        uri = %req.uri(),
+        client_ip = %client_ip,
+        user = tracing::field::Empty,
+        client_cert = tracing::field::Empty,
+        status = tracing::field::Empty,
+        latency_ms = tracing::field::Empty,
+    );
+    // 상위 서비스에서 보낸 traceparent가 있으면 그 trace의 자식 span이 됩니다.
+    span.set_parent(telemetry::extract_parent_context(req.headers()));
 
-    // println!("--> {} {} {}", client_ip, method, uri);
+    async move {
+        let response = next.run(req).await;
+        let elapsed = start.elapsed();
+        let latency_ms = elapsed.as_millis() as u64;
 
-    let response = next.run(req).await;
-    let duration = start.elapsed();
+        let span = tracing::Span::current();
+        span.record("status", response.status().as_u16());
+        span.record("latency_ms", latency_ms);
 
-    println!("|-> {} {} {} ({}) [{} ms]", client_ip, method, uri, response.status(), duration.as_millis());
+        if response.status().is_server_error() {
+            span.record("otel.status_code", "ERROR");
+            tracing::error!(status = response.status().as_u16(), latency_ms, "request failed");
+        } else {
+            tracing::info!(status = response.status().as_u16(), latency_ms, "request completed");
+        }
+        SLOW_EVENTS.record_request(method.as_str(), &route, elapsed);
 
-    response
+        response
+    }
+    .instrument(span)
+    .await
 }
-
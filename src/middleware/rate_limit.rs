@@ -0,0 +1,328 @@
+// 토큰 버킷(token bucket) 방식의 요청 제한
+//
+// - 키: 검증된 자격 증명("admin", "token:<id>")이 있으면 그것을, 없으면 "ip:<client ip>"를 사용합니다.
+//   검증되지 않은 Bearer 토큰은 IP 키로 취급하므로, 임의의 토큰을 바꿔 가며 제한을 피할 수 없습니다.
+// - quota: 기본값(RATE_LIMIT_DEFAULT), 키별(RATE_LIMIT_KEYS), 라우트별(RATE_LIMIT_ROUTES)
+// - 응답 헤더: RateLimit-Limit / RateLimit-Remaining / RateLimit-Reset, 429일 때 Retry-After
+//
+// 환경 변수 예:
+//   RATE_LIMIT_ENABLED=true
+//   RATE_LIMIT_DEFAULT=120/60                               # 60초에 120회
+//   RATE_LIMIT_ROUTES=/axum-users=30/60,/create-user-db=10/60  # 매칭된 라우트 템플릿 기준
+//   RATE_LIMIT_KEYS=admin=1000/60,token:12=300/60
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{header, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::db::DbPool;
+use std::collections::HashMap;
+use std::env;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use super::ClientIp;
+use crate::auth;
+use crate::metrics::observe_query;
+use crate::{AppError, AppState};
+
+// 검증된 Bearer 토큰을 다시 DB에서 확인하기 전까지 캐시해 두는 시간
+const TOKEN_CACHE_TTL: Duration = Duration::from_secs(60);
+// 이 개수를 넘으면 가득 찬(=오래 쓰이지 않은) 버킷을 정리합니다. 정리는 PRUNE_INTERVAL에 한 번만 합니다.
+const MAX_BUCKETS_BEFORE_PRUNE: usize = 10_000;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// `limit`회 요청을 `period` 동안 허용합니다. 버킷 용량도 `limit`입니다.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quota {
+    pub limit: u32,
+    pub period: Duration,
+}
+
+impl Quota {
+    /// "120/60" 형식 (요청 수 / 초)
+    pub fn parse(s: &str) -> Option<Quota> {
+        let (limit, secs) = s.trim().split_once('/')?;
+        let limit: u32 = limit.trim().parse().ok()?;
+        let secs: u64 = secs.trim().parse().ok()?;
+        if limit == 0 || secs == 0 {
+            return None;
+        }
+        Some(Quota { limit, period: Duration::from_secs(secs) })
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.limit as f64 / self.period.as_secs_f64()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub default_quota: Quota,
+    pub route_quotas: HashMap<String, Quota>,
+    pub key_quotas: HashMap<String, Quota>,
+}
+
+fn parse_quota_map(s: &str) -> Result<HashMap<String, Quota>, String> {
+    s.split(',')
+        .filter_map(|entry| {
+            let (name, quota) = entry.trim().rsplit_once('=')?;
+            Some(match Quota::parse(quota) {
+                Some(quota) => Ok((name.trim().to_string(), quota)),
+                None => Err(format!("invalid rate limit quota '{}'", entry)),
+            })
+        })
+        .collect()
+}
+
+impl RateLimitConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Ok(RateLimitConfig {
+            enabled: env::var("RATE_LIMIT_ENABLED").map(|v| v == "true").unwrap_or(true),
+            default_quota: env::var("RATE_LIMIT_DEFAULT")
+                .ok()
+                .map(|v| Quota::parse(&v).ok_or_else(|| format!("RATE_LIMIT_DEFAULT must look like 120/60, got '{}'", v)))
+                .transpose()?
+                .unwrap_or(Quota { limit: 120, period: Duration::from_secs(60) }),
+            route_quotas: parse_quota_map(&env::var("RATE_LIMIT_ROUTES").unwrap_or_default())?,
+            key_quotas: parse_quota_map(&env::var("RATE_LIMIT_KEYS").unwrap_or_default())?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// 버킷이 다시 가득 찰 때까지 남은 시간
+    pub reset_after: Duration,
+    /// 거부된 경우, 토큰 1개가 채워질 때까지 남은 시간
+    pub retry_after: Duration,
+}
+
+pub type StoreFuture<'a> = Pin<Box<dyn Future<Output = Vec<RateLimitDecision>> + Send + 'a>>;
+
+/// 버킷 저장소. 기본은 프로세스 메모리(`InMemoryStore`)이며,
+/// 여러 인스턴스가 quota를 공유해야 하면 Redis 등으로 구현해 `RateLimiter::with_store`에 넘깁니다.
+pub trait RateLimitStore: Send + Sync {
+    /// 버킷마다 토큰 하나를 소비하고 버킷 순서대로 결정을 반환합니다.
+    /// 하나라도 토큰이 없으면 어느 버킷에서도 소비하지 않습니다.
+    fn acquire<'a>(&'a self, buckets: &'a [(String, Quota)]) -> StoreFuture<'a>;
+}
+
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+    /// 버킷을 만든 quota의 period. 정리할 때 이 시간이 지났으면 가득 찬 버킷입니다.
+    period: Duration,
+}
+
+impl Bucket {
+    fn decision(&self, quota: Quota, allowed: bool) -> RateLimitDecision {
+        let capacity = quota.limit as f64;
+        let rate = quota.refill_per_sec();
+        RateLimitDecision {
+            allowed,
+            limit: quota.limit,
+            remaining: self.tokens.floor() as u32,
+            reset_after: Duration::from_secs_f64((capacity - self.tokens) / rate),
+            retry_after: if allowed {
+                Duration::ZERO
+            } else {
+                Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / rate)
+            },
+        }
+    }
+}
+
+struct Buckets {
+    map: HashMap<String, Bucket>,
+    last_prune: Instant,
+}
+
+pub struct InMemoryStore {
+    buckets: Mutex<Buckets>,
+}
+
+impl Default for InMemoryStore {
+    fn default() -> Self {
+        InMemoryStore {
+            buckets: Mutex::new(Buckets { map: HashMap::new(), last_prune: Instant::now() }),
+        }
+    }
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 저장된 버킷 수
+    pub fn len(&self) -> usize {
+        self.buckets.lock().unwrap().map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `now` 시각 기준으로 `acquire`합니다.
+    pub fn acquire_at(&self, buckets: &[(String, Quota)], now: Instant) -> Vec<RateLimitDecision> {
+        let mut store = self.buckets.lock().unwrap();
+
+        if store.map.len() > MAX_BUCKETS_BEFORE_PRUNE && now.duration_since(store.last_prune) >= PRUNE_INTERVAL {
+            // 마지막 사용 이후 자기 period가 지난 버킷은 이미 가득 찬 상태이므로 지워도 결과가 같습니다.
+            store.map.retain(|_, b| now.duration_since(b.last) < b.period);
+            store.last_prune = now;
+        }
+
+        for (name, quota) in buckets {
+            let capacity = quota.limit as f64;
+            let b = store
+                .map
+                .entry(name.clone())
+                .or_insert(Bucket { tokens: capacity, last: now, period: quota.period });
+            b.tokens = (b.tokens + now.duration_since(b.last).as_secs_f64() * quota.refill_per_sec()).min(capacity);
+            b.last = now;
+            b.period = quota.period;
+        }
+
+        let allowed = buckets.iter().all(|(name, _)| store.map[name].tokens >= 1.0);
+        buckets
+            .iter()
+            .map(|(name, quota)| {
+                let b = store.map.get_mut(name).unwrap();
+                if allowed {
+                    b.tokens -= 1.0;
+                }
+                b.decision(*quota, allowed)
+            })
+            .collect()
+    }
+}
+
+impl RateLimitStore for InMemoryStore {
+    fn acquire<'a>(&'a self, buckets: &'a [(String, Quota)]) -> StoreFuture<'a> {
+        let decisions = self.acquire_at(buckets, Instant::now());
+        Box::pin(async move { decisions })
+    }
+}
+
+pub struct RateLimiter {
+    pub config: RateLimitConfig,
+    store: Arc<dyn RateLimitStore>,
+    // token_hash -> (token id, 확인 시각)
+    verified_tokens: Mutex<HashMap<String, (i32, Instant)>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self::with_store(config, Arc::new(InMemoryStore::new()))
+    }
+
+    pub fn with_store(config: RateLimitConfig, store: Arc<dyn RateLimitStore>) -> Self {
+        RateLimiter {
+            config,
+            store,
+            verified_tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 라우트 quota와 키 quota를 함께 확인합니다. 한쪽이라도 거부하면 어느 쪽도 소비하지 않습니다.
+    /// 거부되면 거부한 버킷의 결정을, 허용되면 더 적게 남은 쪽의 결정을 반환합니다.
+    pub async fn check(&self, route: &str, key: &str) -> RateLimitDecision {
+        let key_quota = self.config.key_quotas.get(key).copied().unwrap_or(self.config.default_quota);
+        let mut buckets = vec![(format!("*|{}", key), key_quota)];
+        if let Some(quota) = self.config.route_quotas.get(route) {
+            buckets.push((format!("{}|{}", route, key), *quota));
+        }
+
+        let decisions = self.store.acquire(&buckets).await;
+        let denied = decisions.iter().filter(|d| !d.allowed).max_by_key(|d| d.retry_after);
+        *denied
+            .or_else(|| decisions.iter().min_by_key(|d| d.remaining))
+            .expect("the key bucket is always checked")
+    }
+
+    /// Bearer 토큰이 유효하면 "token:<id>"를 반환합니다. 결과는 잠시 캐시합니다.
+    async fn verified_token_key(&self, db_pool: &DbPool, token: &str) -> Option<String> {
+        let hash = auth::hash_token(token);
+        if let Some((id, at)) = self.verified_tokens.lock().unwrap().get(&hash)
+            && at.elapsed() < TOKEN_CACHE_TTL
+        {
+            return Some(format!("token:{}", id));
+        }
+
+        let id: i32 = observe_query(
+            "find_token_id",
+            sqlx::query_scalar("SELECT id FROM axum_api_tokens WHERE token_hash = ? AND revoked_at IS NULL")
+                .bind(&hash),
+            |q| q.fetch_optional(db_pool),
+        )
+        .await
+        .ok()
+        .flatten()?;
+
+        let mut cache = self.verified_tokens.lock().unwrap();
+        cache.retain(|_, (_, at)| at.elapsed() < TOKEN_CACHE_TTL);
+        cache.insert(hash, (id, Instant::now()));
+        Some(format!("token:{}", id))
+    }
+}
+
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    let secs = |d: Duration| d.as_secs_f64().ceil() as u64;
+    headers.insert("ratelimit-limit", HeaderValue::from(decision.limit));
+    headers.insert("ratelimit-remaining", HeaderValue::from(decision.remaining));
+    headers.insert("ratelimit-reset", HeaderValue::from(secs(decision.reset_after)));
+    if !decision.allowed {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(secs(decision.retry_after).max(1)));
+    }
+}
+
+pub async fn rate_limit_middleware(
+    State(app_state): State<Arc<AppState>>,
+    client_ip: ClientIp,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let limiter = &app_state.rate_limiter;
+    if !limiter.config.enabled {
+        return next.run(req).await;
+    }
+
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "*".to_string());
+
+    let admin_key = req.headers().get("X-Admin-API-Key").and_then(|v| v.to_str().ok());
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    let credential_key = match (admin_key, bearer, req.extensions().get::<DbPool>()) {
+        (Some(key), _, _) if key == app_state.admin_api_key => Some("admin".to_string()),
+        (None, Some(token), Some(db_pool)) => limiter.verified_token_key(db_pool, token).await,
+        _ => None,
+    };
+    let key = credential_key.unwrap_or_else(|| format!("ip:{}", client_ip));
+
+    let decision = limiter.check(&route, &key).await;
+    let mut response = if decision.allowed {
+        next.run(req).await
+    } else {
+        AppError::RateLimited(format!("rate limit exceeded for {}", route)).into_response()
+    };
+    set_rate_limit_headers(response.headers_mut(), &decision);
+    response
+}
@@ -1,32 +1,2 @@
-use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
-
-#[derive(Deserialize, Serialize, ToSchema, Debug)]
-pub struct User {
-    pub id: i32,
-    pub name: String,
-    pub email: String,
-}
-
-#[derive(Serialize, ToSchema)]
-pub struct UserItem {
-    pub id: i32,
-    pub name: String,
-}
-
-#[derive(Deserialize, ToSchema)]
-pub struct Page {
-    pub number: u32,
-}
-
-#[derive(Deserialize, ToSchema)]
-pub struct BodyItem {
-    pub title: String,
-}
-
-
-#[derive(Deserialize, ToSchema)]
-pub struct CreateUserRequest {
-    pub name: String,
-    pub email: String,
-}
\ No newline at end of file
+// 요청/응답 타입은 클라이언트 crate와 같이 쓰도록 crates/axum-rest-api-models 에 있습니다.
+pub use axum_rest_api_models::*;
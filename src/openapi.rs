@@ -0,0 +1,211 @@
+// OpenAPI 문서 (Swagger UI: /swagger-ui)
+// - /api-docs/v1/openapi.json: /api/v1 리소스 경로 (`ApiDocV1`)
+// - /api-docs/openapi.json: 버전 없는 예전 경로 (`ApiDoc`, 폐기 예정 operation은 deprecated로 표시)
+use utoipa::OpenApi;
+use utoipa::Modify; // Modify 트레잇 임포트
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme}; // ApiKeyValue 임포트 확인
+use utoipa::openapi::{Content, Deprecated, RefOr, ResponseBuilder, Schema};
+use crate::{handlers, middleware, models};
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "ApiKeyAuth", // 보안 스키마 이름
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Admin-API-Key"))), // ApiKeyValue 사용
+        );
+        components.add_security_scheme(
+            "BearerAuth", // 개인 액세스 토큰 (Authorization: Bearer arat_...)
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        // 여기에 핸들러 함수들을 등록합니다.
+        handlers::user::create_user,
+        handlers::user::create_user_db,
+        handlers::user::list_users,
+        handlers::user::list_users_db,
+        handlers::user::delete_user,
+        handlers::user::get_app_state,
+        handlers::item::show_item,
+        handlers::item::add_item,
+        handlers::token::create_token,
+        handlers::token::list_tokens,
+        handlers::token::revoke_token,
+        handlers::oidc::oidc_login,
+        handlers::oidc::oidc_callback,
+        handlers::admin::list_slow_events,
+        handlers::ws::ws_handler,
+        handlers::health::health,
+        handlers::health::ready,
+    ),
+    components(
+        schemas(
+            // 여기에 API에서 사용하는 모델(스키마)들을 등록합니다.
+            models::User,
+            models::UserItem,
+            models::Page,
+            models::BodyItem,
+            models::CreateUserRequest,
+            models::CreateTokenRequest,
+            models::ApiToken,
+            models::CreatedApiToken,
+            models::OidcLoginResponse,
+            models::SlowEventKind,
+            models::SlowEvent,
+            models::ErrorResponse,
+        )
+        // security_schemes 직접 정의 제거
+    ),
+    modifiers(&SecurityAddon, &LegacyDeprecations, &BinaryFormats), // modifiers를 사용하여 보안 스키마 추가
+    tags(
+        (name = "axum-rest-api", description = "Axum REST API endpoints")
+    )
+    // security(...) // 전역 보안 요구사항은 여기서 정의 가능
+)]
+pub struct ApiDoc;
+
+/// `middleware::LEGACY_ROUTES`의 operation을 deprecated로 표시합니다.
+/// utoipa는 핸들러의 `#[deprecated]`로만 표시할 수 있는데, 그러면 호출하는 곳마다 경고가 나므로 문서에서 바꿉니다.
+struct LegacyDeprecations;
+
+impl Modify for LegacyDeprecations {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for (legacy, _) in middleware::LEGACY_ROUTES {
+            // axum 경로(/delete-user/:id) -> OpenAPI 경로(/delete-user/{id})
+            let path = legacy
+                .split('/')
+                .map(|segment| match segment.strip_prefix(':') {
+                    Some(name) => format!("{{{}}}", name),
+                    None => segment.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            if let Some(item) = openapi.paths.paths.get_mut(&path) {
+                for operation in item.operations.values_mut() {
+                    operation.deprecated = Some(Deprecated::True);
+                }
+            }
+        }
+    }
+}
+
+/// 모델(스키마 참조)을 주고받는 operation에 MessagePack, CBOR 본문을 추가합니다 (`handlers::negotiate`).
+/// 응답은 2xx만 해당하고, 오류 응답(ErrorResponse)은 항상 JSON입니다.
+struct BinaryFormats;
+
+fn references_model(schema: &RefOr<Schema>) -> bool {
+    match schema {
+        RefOr::Ref(_) => true,
+        RefOr::T(Schema::Array(array)) => matches!(*array.items, RefOr::Ref(_)),
+        RefOr::T(_) => false,
+    }
+}
+
+fn binary_variants(json: &Content) -> [(String, Content); 2] {
+    [
+        (models::format::MSGPACK.to_string(), json.clone()),
+        (models::format::CBOR.to_string(), json.clone()),
+    ]
+}
+
+fn error_response(description: &str) -> RefOr<utoipa::openapi::Response> {
+    ResponseBuilder::new()
+        .description(description)
+        .content(models::format::JSON, Content::new(RefOr::Ref(utoipa::openapi::Ref::from_schema_name("ErrorResponse"))))
+        .build()
+        .into()
+}
+
+impl Modify for BinaryFormats {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let operations = openapi.paths.paths.values_mut().flat_map(|item| item.operations.values_mut());
+        for operation in operations {
+            if let Some(body) = operation.request_body.as_mut()
+                && let Some(json) = body.content.get(models::format::JSON).filter(|c| references_model(&c.schema))
+            {
+                let variants = binary_variants(json);
+                body.content.extend(variants);
+                operation.responses.responses.entry("415".to_string()).or_insert_with(|| {
+                    error_response("Content-Type is not application/json, application/msgpack or application/cbor")
+                });
+            }
+
+            let mut negotiated = false;
+            for (status, response) in operation.responses.responses.iter_mut() {
+                if let RefOr::T(response) = response
+                    && status.starts_with('2')
+                    && let Some(json) = response.content.get(models::format::JSON).filter(|c| references_model(&c.schema))
+                {
+                    let variants = binary_variants(json);
+                    response.content.extend(variants);
+                    negotiated = true;
+                }
+            }
+            if negotiated {
+                operation.responses.responses.entry("406".to_string()).or_insert_with(|| {
+                    error_response("Accept does not allow application/json, application/msgpack or application/cbor")
+                });
+            }
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        handlers::v1::list_users,
+        handlers::v1::create_user,
+        handlers::v1::get_user,
+        handlers::v1::update_user,
+        handlers::v1::delete_user,
+        handlers::v1::user_events,
+        handlers::v1::get_item,
+        handlers::v1::create_item,
+        handlers::v1::create_token,
+        handlers::v1::list_tokens,
+        handlers::v1::revoke_token,
+        // 버전이 없는 운영/인증 경로는 두 문서에 모두 실립니다.
+        handlers::user::get_app_state,
+        handlers::oidc::oidc_login,
+        handlers::oidc::oidc_callback,
+        handlers::admin::list_slow_events,
+        handlers::ws::ws_handler,
+        handlers::health::health,
+        handlers::health::ready,
+    ),
+    components(
+        schemas(
+            models::User,
+            models::BodyItem,
+            models::CreateUserRequest,
+            models::UpdateUserRequest,
+            models::UserChange,
+            models::UserEvent,
+            models::ItemChange,
+            models::ItemEvent,
+            models::WsTopic,
+            models::WsRequest,
+            models::WsMessage,
+            models::CreateTokenRequest,
+            models::ApiToken,
+            models::CreatedApiToken,
+            models::OidcLoginResponse,
+            models::SlowEventKind,
+            models::SlowEvent,
+            models::ErrorResponse,
+        )
+    ),
+    modifiers(&SecurityAddon, &BinaryFormats),
+    info(version = "1"),
+    tags(
+        (name = "axum-rest-api", description = "Axum REST API endpoints")
+    )
+)]
+pub struct ApiDocV1;
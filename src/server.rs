@@ -0,0 +1,220 @@
+// HTTP 서버 accept 루프와 종료 처리
+//
+// axum::serve는 연결 단위 설정을 노출하지 않으므로 hyper-util로 연결을 직접 받아
+// 헤더 읽기 제한 시간(HEADER_READ_TIMEOUT_SECS)을 적용합니다.
+// 요청마다 `ConnectInfo<SocketAddr>`를 넣어 주므로 `into_make_service_with_connect_info`와 같게 동작합니다.
+// 리스너는 `listener::Accept`를 구현한 TCP / Unix 소켓 모두 쓸 수 있습니다.
+// TLS를 쓰면 핸드셰이크도 연결 task 안에서 하므로 느린 핸드셰이크가 accept 루프를 막지 않습니다.
+//
+// 종료 순서 (SIGTERM / SIGINT):
+//   1. `AppState::shutdown` 토큰 취소 -> /ready 가 503, 백그라운드 작업에 알림
+//   2. SHUTDOWN_READINESS_DELAY_SECS 동안 계속 요청을 받음 (로드 밸런서가 빠질 시간)
+//   3. 새 연결을 받지 않고, 처리 중인 요청을 SHUTDOWN_DRAIN_TIMEOUT_SECS 까지 기다림
+//   4. 그래도 남은 연결은 중단
+//
+// 환경 변수 예:
+//   SHUTDOWN_READINESS_DELAY_SECS=5   (기본값 5, 0이면 바로 3단계)
+//   SHUTDOWN_DRAIN_TIMEOUT_SECS=30    (기본값 30)
+use axum::{extract::ConnectInfo, http::Request, Router};
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use std::env;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+use crate::listener::Accept;
+use crate::tls::ClientCert;
+
+#[derive(Clone, Debug)]
+pub struct ShutdownConfig {
+    pub readiness_delay: Duration,
+    pub drain_timeout: Duration,
+}
+
+fn env_secs(name: &str, default: u64) -> Result<Duration, String> {
+    let secs = env::var(name)
+        .ok()
+        .map(|v| v.parse().map_err(|_| format!("{} must be a number, got '{}'", name, v)))
+        .transpose()?
+        .unwrap_or(default);
+    Ok(Duration::from_secs(secs))
+}
+
+impl ShutdownConfig {
+    pub fn from_env() -> Result<Self, String> {
+        Ok(ShutdownConfig {
+            readiness_delay: env_secs("SHUTDOWN_READINESS_DELAY_SECS", 5)?,
+            drain_timeout: env_secs("SHUTDOWN_DRAIN_TIMEOUT_SECS", 30)?,
+        })
+    }
+}
+
+/// SIGINT(Ctrl+C) 또는 SIGTERM을 받거나 `token`이 다른 곳에서 취소되면,
+/// 토큰을 취소하고 readiness 지연 시간만큼 기다린 뒤 끝납니다.
+pub async fn shutdown_signal(token: CancellationToken, config: ShutdownConfig) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install CTRL+C signal handler");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM signal handler")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("SIGINT 신호 수신, 서버 종료 시작..."),
+        _ = terminate => tracing::info!("SIGTERM 신호 수신, 서버 종료 시작..."),
+        _ = token.cancelled() => tracing::info!("종료 요청 수신, 서버 종료 시작..."),
+    }
+    token.cancel();
+
+    if !config.readiness_delay.is_zero() {
+        tracing::info!(delay_secs = config.readiness_delay.as_secs(), "readiness를 내리고 대기합니다");
+        tokio::time::sleep(config.readiness_delay).await;
+    }
+}
+
+#[derive(Clone)]
+pub struct ServeOptions {
+    /// 요청 헤더(TLS라면 핸드셰이크 포함)를 다 받을 때까지 기다리는 최대 시간
+    pub header_read_timeout: Duration,
+    pub drain_timeout: Duration,
+    /// `Some`이면 HTTPS
+    pub tls: Option<TlsAcceptor>,
+}
+
+/// `shutdown`이 끝나면 새 연결을 받지 않고, 처리 중인 연결을 `drain_timeout`까지 기다린 뒤 남은 연결을 중단합니다.
+pub async fn serve<L, F>(listener: L, app: Router, options: ServeOptions, shutdown: F)
+where
+    L: Accept,
+    F: Future<Output = ()>,
+{
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .header_read_timeout(options.header_read_timeout);
+    // 취소되면 각 연결이 graceful shutdown을 시작합니다 (keep-alive 종료, HTTP/2 GOAWAY).
+    let draining = CancellationToken::new();
+    let mut connections = JoinSet::new();
+    let mut shutdown = std::pin::pin!(shutdown);
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            conn = listener.accept() => match conn {
+                Ok(conn) => conn,
+                Err(e) => {
+                    // 파일 디스크립터 고갈 등. 잠시 쉬었다가 다시 받습니다.
+                    tracing::error!(error = %e, "failed to accept connection");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            // 끝난 연결 task 정리
+            Some(_) = connections.join_next(), if !connections.is_empty() => continue,
+            _ = shutdown.as_mut() => break,
+        };
+
+        let app = app.clone();
+        let builder = builder.clone();
+        let draining = draining.clone();
+        let tls = options.tls.clone();
+        let handshake_timeout = options.header_read_timeout;
+
+        connections.spawn(async move {
+            let result = match tls {
+                None => {
+                    let service = connection_service(app, remote_addr, None);
+                    serve_connection(&builder, TokioIo::new(stream), service, draining).await
+                }
+                Some(acceptor) => match tokio::time::timeout(handshake_timeout, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => {
+                        let client_cert = stream
+                            .get_ref()
+                            .1
+                            .peer_certificates()
+                            .and_then(|certs| certs.first())
+                            .and_then(ClientCert::from_der);
+                        let service = connection_service(app, remote_addr, client_cert);
+                        serve_connection(&builder, TokioIo::new(stream), service, draining).await
+                    }
+                    Ok(Err(e)) => {
+                        tracing::debug!(error = %e, %remote_addr, "TLS handshake failed");
+                        return;
+                    }
+                    Err(_) => {
+                        tracing::debug!(%remote_addr, "TLS handshake timed out");
+                        return;
+                    }
+                },
+            };
+            if let Err(e) = result {
+                tracing::debug!(error = %e, %remote_addr, "connection closed with error");
+            }
+        });
+    }
+
+    drop(listener);
+    tracing::info!(connections = connections.len(), "새 연결을 받지 않고 처리 중인 요청을 기다립니다");
+
+    // keep-alive 연결에 종료를 알리고, 진행 중인 요청이 끝나기를 기다립니다.
+    draining.cancel();
+    let drain = async { while connections.join_next().await.is_some() {} };
+    if tokio::time::timeout(options.drain_timeout, drain).await.is_err() {
+        tracing::warn!(
+            connections = connections.len(),
+            drain_timeout_secs = options.drain_timeout.as_secs(),
+            "drain 시간이 지나 남은 연결을 중단합니다"
+        );
+        connections.shutdown().await;
+    }
+}
+
+/// 요청마다 연결 정보(원격 주소, 검증된 클라이언트 인증서)를 extensions에 넣는 서비스
+fn connection_service(
+    app: Router,
+    remote_addr: SocketAddr,
+    client_cert: Option<ClientCert>,
+) -> TowerToHyperService<impl tower::Service<Request<Incoming>, Response = axum::response::Response, Error = Infallible, Future: Send> + Clone> {
+    TowerToHyperService::new(app.map_request(move |mut req: Request<Incoming>| {
+        req.extensions_mut().insert(ConnectInfo(remote_addr));
+        if let Some(cert) = &client_cert {
+            req.extensions_mut().insert(cert.clone());
+        }
+        req
+    }))
+}
+
+async fn serve_connection<I, S>(
+    builder: &auto::Builder<TokioExecutor>,
+    io: I,
+    service: S,
+    draining: CancellationToken,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
+    S: hyper::service::Service<Request<Incoming>, Response = axum::response::Response> + Send,
+    S::Future: Send + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let conn = builder.serve_connection_with_upgrades(io, service);
+    let mut conn = std::pin::pin!(conn);
+    tokio::select! {
+        result = conn.as_mut() => return result,
+        _ = draining.cancelled() => conn.as_mut().graceful_shutdown(),
+    }
+    conn.await
+}
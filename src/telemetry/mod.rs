@@ -0,0 +1,110 @@
+// tracing 기반 로깅 / OpenTelemetry trace 설정
+//
+// 환경 변수:
+//   LOG_FORMAT=pretty | json   (기본값 pretty)
+//   RUST_LOG=info,axum_rest_api=debug,sqlx=warn   (EnvFilter directive, 기본값 info)
+//
+//   OTEL_EXPORTER_OTLP_ENDPOINT=http://localhost:4318   (설정하면 OTLP/HTTP로 span을 내보냅니다)
+//   OTEL_EXPORTER_OTLP_TRACES_ENDPOINT=http://collector:4318/v1/traces   (trace 전용 전체 URL, 선택)
+//   OTEL_SERVICE_NAME=axum-rest-api
+//
+// 들어오는 요청의 W3C `traceparent` / `tracestate` 헤더를 읽어 상위 trace에 이어 붙입니다.
+use axum::http::HeaderMap;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::{propagation::TraceContextPropagator, trace::SdkTracerProvider, Resource};
+use std::env;
+use std::sync::OnceLock;
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+static TRACER_PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    pub fn from_env() -> Self {
+        match env::var("LOG_FORMAT").unwrap_or_default().to_ascii_lowercase().as_str() {
+            "json" => LogFormat::Json,
+            _ => LogFormat::Pretty,
+        }
+    }
+}
+
+fn otlp_enabled() -> bool {
+    env::var("OTEL_EXPORTER_OTLP_ENDPOINT").is_ok() || env::var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT").is_ok()
+}
+
+fn build_tracer_provider() -> Result<SdkTracerProvider, opentelemetry::trace::TraceError> {
+    let exporter = opentelemetry_otlp::SpanExporter::builder().with_http().build()?;
+
+    let mut resource = Resource::builder();
+    if env::var("OTEL_SERVICE_NAME").is_err() {
+        resource = resource.with_service_name(env!("CARGO_PKG_NAME"));
+    }
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build())
+}
+
+/// 전역 subscriber를 설치합니다. 이미 설치되어 있으면(테스트 등) 아무것도 하지 않습니다.
+pub fn init_tracing() {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let otel_layer = if otlp_enabled() {
+        match build_tracer_provider() {
+            Ok(provider) => {
+                let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+                let _ = TRACER_PROVIDER.set(provider);
+                Some(tracing_opentelemetry::layer().with_tracer(tracer))
+            }
+            Err(e) => {
+                eprintln!("failed to initialize OTLP exporter: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let registry = tracing_subscriber::registry().with(filter).with(otel_layer);
+
+    let _ = match LogFormat::from_env() {
+        LogFormat::Json => registry
+            .with(fmt::layer().json().with_current_span(true).with_span_list(false))
+            .try_init(),
+        LogFormat::Pretty => registry.with(fmt::layer().with_target(false)).try_init(),
+    };
+}
+
+/// 종료 시 아직 내보내지 않은 span을 flush 합니다.
+pub fn shutdown_tracing() {
+    if let Some(provider) = TRACER_PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!(error = %e, "failed to shut down tracer provider");
+    }
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+/// 요청 헤더의 `traceparent` / `tracestate`에서 상위 context를 꺼냅니다.
+pub fn extract_parent_context(headers: &HeaderMap) -> opentelemetry::Context {
+    opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
@@ -0,0 +1,313 @@
+// rustls 기반 HTTPS
+//
+// TLS_CERT_PATH / TLS_KEY_PATH를 모두 설정하면 HTTPS로 서비스합니다. ALPN으로 h2, http/1.1을 광고합니다.
+// 인증서는 파일이 바뀌거나(TLS_RELOAD_INTERVAL_SECS 간격으로 수정 시각 확인) SIGHUP을 받으면
+// 재시작 없이 다시 읽습니다. 새 인증서를 읽지 못하면 기존 인증서를 계속 사용합니다.
+// TLS_REDIRECT_PORT를 설정하면 그 포트의 HTTP 요청을 HTTPS로 308 리다이렉트합니다.
+//
+// mTLS: TLS_CLIENT_CA_PATH를 설정하면 그 CA가 서명한 클라이언트 인증서를 받습니다(선택 사항).
+// 인증서를 제시하지 않은 연결도 받으며, 검증된 인증서의 subject는 `ClientCert`로 요청에 들어갑니다.
+// ADMIN_REQUIRE_CLIENT_CERT=true이면 관리자 접근에 관리자 키와 클라이언트 인증서가 모두 필요합니다.
+// CA 파일 변경은 재시작해야 반영됩니다.
+//
+// 환경 변수 예:
+//   TLS_CERT_PATH=./certs/cert.pem     (체인 포함 PEM)
+//   TLS_KEY_PATH=./certs/key.pem       (PKCS#8, PKCS#1, SEC1 PEM)
+//   TLS_RELOAD_INTERVAL_SECS=10
+//   TLS_REDIRECT_PORT=3080
+//   TLS_CLIENT_CA_PATH=./certs/client-ca.pem
+//   ADMIN_REQUIRE_CLIENT_CERT=true
+//   ADMIN_CLIENT_CERT_SUBJECTS=CN=ops-admin;CN=backup-bot,O=Example   (';'로 구분, 비우면 CA가 서명한 인증서 모두 허용)
+//
+// 로컬 테스트용 자체 서명 인증서는 ./gen-dev-cert.sh 로 만들 수 있습니다.
+use axum::{
+    extract::Host,
+    http::{StatusCode, Uri},
+    response::{IntoResponse, Redirect},
+    routing::any,
+    Router,
+};
+use rustls::crypto::ring;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    pub reload_interval: Duration,
+    pub redirect_port: Option<u16>,
+    pub client_ca_path: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// 인증서와 키 경로가 모두 있으면 `Some`
+    pub fn from_env() -> Result<Option<Self>, String> {
+        let cert_path = env::var("TLS_CERT_PATH").ok().filter(|v| !v.is_empty());
+        let key_path = env::var("TLS_KEY_PATH").ok().filter(|v| !v.is_empty());
+        let (cert_path, key_path) = match (cert_path, key_path) {
+            (Some(cert), Some(key)) => (cert, key),
+            (None, None) => return Ok(None),
+            _ => return Err("TLS_CERT_PATH and TLS_KEY_PATH must be set together".to_string()),
+        };
+
+        Ok(Some(TlsConfig {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            reload_interval: Duration::from_secs(
+                env::var("TLS_RELOAD_INTERVAL_SECS")
+                    .ok()
+                    .map(|v| v.parse().map_err(|_| format!("TLS_RELOAD_INTERVAL_SECS must be a number, got '{}'", v)))
+                    .transpose()?
+                    .unwrap_or(10),
+            ),
+            redirect_port: env::var("TLS_REDIRECT_PORT")
+                .ok()
+                .map(|v| v.parse().map_err(|_| format!("TLS_REDIRECT_PORT must be a port number, got '{}'", v)))
+                .transpose()?,
+            client_ca_path: env::var("TLS_CLIENT_CA_PATH").ok().filter(|v| !v.is_empty()).map(PathBuf::from),
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub enum TlsError {
+    Io(PathBuf, std::io::Error),
+    NoCertificate(PathBuf),
+    NoPrivateKey(PathBuf),
+    Rustls(rustls::Error),
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsError::Io(path, e) => write!(f, "failed to read {}: {}", path.display(), e),
+            TlsError::NoCertificate(path) => write!(f, "no certificate found in {}", path.display()),
+            TlsError::NoPrivateKey(path) => write!(f, "no private key found in {}", path.display()),
+            TlsError::Rustls(e) => write!(f, "invalid certificate or key: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TlsError {}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let file = File::open(path).map_err(|e| TlsError::Io(path.into(), e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TlsError::Io(path.into(), e))?;
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificate(path.into()));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, TlsError> {
+    let file = File::open(path).map_err(|e| TlsError::Io(path.into(), e))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| TlsError::Io(path.into(), e))?
+        .ok_or_else(|| TlsError::NoPrivateKey(path.into()))
+}
+
+fn load_certified_key(config: &TlsConfig) -> Result<CertifiedKey, TlsError> {
+    let certs = load_certs(&config.cert_path)?;
+    let key = load_key(&config.key_path)?;
+    let certified = CertifiedKey::from_der(certs, key, &ring::default_provider()).map_err(TlsError::Rustls)?;
+    // 인증서와 키가 짝이 맞지 않으면 여기서 거부합니다.
+    certified.keys_match().map_err(TlsError::Rustls)?;
+    Ok(certified)
+}
+
+/// 핸드셰이크마다 현재 인증서를 돌려주는 resolver. `reload`로 교체합니다.
+pub struct ReloadableCert {
+    config: TlsConfig,
+    current: RwLock<Arc<CertifiedKey>>,
+}
+
+impl fmt::Debug for ReloadableCert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadableCert").field("config", &self.config).finish()
+    }
+}
+
+impl ReloadableCert {
+    pub fn load(config: TlsConfig) -> Result<Self, TlsError> {
+        let certified = load_certified_key(&config)?;
+        Ok(ReloadableCert {
+            config,
+            current: RwLock::new(Arc::new(certified)),
+        })
+    }
+
+    /// 파일을 다시 읽어 교체합니다. 실패하면 기존 인증서를 유지합니다.
+    pub fn reload(&self) -> Result<(), TlsError> {
+        let certified = load_certified_key(&self.config)?;
+        *self.current.write().unwrap() = Arc::new(certified);
+        Ok(())
+    }
+
+    fn modified(&self) -> Option<(SystemTime, SystemTime)> {
+        let cert = std::fs::metadata(&self.config.cert_path).and_then(|m| m.modified()).ok()?;
+        let key = std::fs::metadata(&self.config.key_path).and_then(|m| m.modified()).ok()?;
+        Some((cert, key))
+    }
+}
+
+impl ResolvesServerCert for ReloadableCert {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().unwrap().clone())
+    }
+}
+
+/// `TlsAcceptor`를 만듭니다. 인증서 재로드는 `spawn_reloader`로 시작합니다.
+pub fn acceptor(cert: Arc<ReloadableCert>) -> Result<TlsAcceptor, TlsError> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .expect("default TLS protocol versions are supported");
+
+    let builder = match &cert.config.client_ca_path {
+        None => builder.with_no_client_auth(),
+        Some(ca_path) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for ca in load_certs(ca_path)? {
+                roots.add(ca).map_err(TlsError::Rustls)?;
+            }
+            // 인증서 없는 클라이언트도 받습니다. 필요 여부는 라우트(관리자)에서 판단합니다.
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .allow_unauthenticated()
+                .build()
+                .map_err(|e| TlsError::Rustls(rustls::Error::General(e.to_string())))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+    };
+
+    let mut server_config = builder.with_cert_resolver(cert);
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// TLS 핸드셰이크에서 검증된 클라이언트 인증서. 인증서를 제시한 연결의 요청에만 extensions로 들어갑니다.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientCert {
+    /// "CN=ops-admin, O=Example" 형식
+    pub subject: String,
+}
+
+impl ClientCert {
+    pub fn from_der(der: &CertificateDer<'_>) -> Option<Self> {
+        let (_, cert) = x509_parser::parse_x509_certificate(der.as_ref()).ok()?;
+        Some(ClientCert { subject: cert.subject().to_string() })
+    }
+}
+
+/// 관리자 접근에 클라이언트 인증서를 요구하는 정책
+#[derive(Clone, Debug, Default)]
+pub struct ClientCertPolicy {
+    pub required_for_admin: bool,
+    /// 비어 있으면 CA가 서명한 인증서는 모두 허용
+    pub allowed_subjects: Vec<String>,
+}
+
+/// subject 비교용: 공백을 없애고 소문자로
+fn normalize_subject(subject: &str) -> String {
+    subject.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_lowercase()
+}
+
+impl ClientCertPolicy {
+    pub fn from_env() -> Self {
+        ClientCertPolicy {
+            required_for_admin: env::var("ADMIN_REQUIRE_CLIENT_CERT").map(|v| v == "true").unwrap_or(false),
+            allowed_subjects: env::var("ADMIN_CLIENT_CERT_SUBJECTS")
+                .unwrap_or_default()
+                .split(';')
+                .map(normalize_subject)
+                .filter(|s| !s.is_empty())
+                .collect(),
+        }
+    }
+
+    /// 관리자 접근을 허용할지 확인합니다. 거부하면 이유를 돌려줍니다.
+    pub fn check_admin(&self, cert: Option<&ClientCert>) -> Result<(), String> {
+        if !self.required_for_admin {
+            return Ok(());
+        }
+        let Some(cert) = cert else {
+            return Err("client certificate required for admin access".to_string());
+        };
+        if self.allowed_subjects.is_empty() || self.allowed_subjects.contains(&normalize_subject(&cert.subject)) {
+            Ok(())
+        } else {
+            Err(format!("client certificate '{}' is not allowed for admin access", cert.subject))
+        }
+    }
+}
+
+/// 파일 수정 시각을 주기적으로 확인하고, SIGHUP을 받으면 인증서를 다시 읽습니다. `token`이 취소되면 끝납니다.
+pub fn spawn_reloader(cert: Arc<ReloadableCert>, token: CancellationToken) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(cert.config.reload_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_modified = cert.modified();
+
+        #[cfg(unix)]
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+            .expect("failed to install SIGHUP signal handler");
+
+        loop {
+            #[cfg(unix)]
+            let sighup = hangup.recv();
+            #[cfg(not(unix))]
+            let sighup = std::future::pending::<Option<()>>();
+
+            let reason = tokio::select! {
+                _ = token.cancelled() => break,
+                _ = sighup => "SIGHUP",
+                _ = interval.tick() => {
+                    let modified = cert.modified();
+                    if modified.is_none() || modified == last_modified {
+                        continue;
+                    }
+                    last_modified = modified;
+                    "file change"
+                }
+            };
+
+            match cert.reload() {
+                Ok(()) => tracing::info!(reason, "TLS certificate reloaded"),
+                Err(e) => tracing::error!(reason, error = %e, "failed to reload TLS certificate, keeping the previous one"),
+            }
+        }
+    });
+}
+
+/// HTTP 요청을 같은 호스트의 HTTPS 포트로 리다이렉트하는 라우터
+pub fn redirect_router(https_port: u16) -> Router {
+    Router::new().fallback(any(move |Host(host): Host, uri: Uri| async move {
+        // Host 헤더의 포트는 HTTP 포트이므로 떼어 내고 HTTPS 포트를 붙입니다.
+        let hostname = match host.rsplit_once(':') {
+            Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name.to_string(),
+            _ => host,
+        };
+        let authority = if https_port == 443 {
+            hostname
+        } else {
+            format!("{}:{}", hostname, https_port)
+        };
+        let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+        match format!("https://{}{}", authority, path).parse::<Uri>() {
+            Ok(target) => Redirect::permanent(&target.to_string()).into_response(),
+            Err(_) => StatusCode::BAD_REQUEST.into_response(),
+        }
+    }))
+}
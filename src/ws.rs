@@ -0,0 +1,343 @@
+// 실시간 변경 알림 (GET /ws, WebSocket)
+//
+// 인증된 클라이언트(관리자 키 또는 Bearer 토큰)가 텍스트 메시지로 토픽을 구독합니다.
+//   → {"type":"subscribe","topics":["users","items"]}
+//   ← {"type":"subscribed","topics":["users","items"]}
+//   ← {"type":"user","event":{UserEvent}}, {"type":"item","event":{ItemEvent}}
+// `users` 토픽은 `users:read` 스코프가 필요합니다. 브라우저 WebSocket은 헤더를 붙일 수 없으므로
+// 서버 사이 연동이나 `axum-rest-api-client` 용입니다. SSE(/api/v1/users/events)도 같은 인증을 요구합니다.
+//
+// 연결마다 전송 대기열(WS_SEND_QUEUE)이 있습니다. 클라이언트가 느려 대기열이 차면 이벤트를 버리고,
+// 다음에 보낼 수 있을 때 `lagged`(버린 수)를 먼저 보냅니다. 프레임 하나를 WS_SEND_TIMEOUT_SECS 안에
+// 보내지 못하면 연결을 끊습니다. 서버는 WS_PING_INTERVAL_SECS마다 ping을 보내고, 그 뒤 WS_PONG_TIMEOUT_SECS
+// 안에 아무 프레임(pong 포함)도 오지 않으면 끊긴 연결로 보고 닫습니다.
+//
+// 연결 수는 GET /admin/get_app_state 의 `websocket`에서 볼 수 있습니다.
+//
+// 환경 변수:
+//   WS_MAX_CONNECTIONS=1000     동시 연결 수. 넘으면 503
+//   WS_SEND_QUEUE=64            연결마다 쌓아 둘 수 있는 메시지 수
+//   WS_SEND_TIMEOUT_SECS=10
+//   WS_PING_INTERVAL_SECS=30
+//   WS_PONG_TIMEOUT_SECS=10
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket};
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::env;
+use std::future::pending;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{interval_at, sleep_until, timeout, Instant};
+use crate::auth::{Principal, SCOPE_USERS_READ};
+use crate::models::{WsMessage, WsRequest, WsTopic};
+use crate::AppState;
+
+#[derive(Clone, Debug)]
+pub struct WsConfig {
+    pub max_connections: usize,
+    pub send_queue: usize,
+    pub send_timeout: Duration,
+    pub ping_interval: Duration,
+    pub pong_timeout: Duration,
+}
+
+impl WsConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        WsConfig {
+            max_connections: var("WS_MAX_CONNECTIONS", 1000) as usize,
+            send_queue: var("WS_SEND_QUEUE", 64) as usize,
+            send_timeout: Duration::from_secs(var("WS_SEND_TIMEOUT_SECS", 10)),
+            ping_interval: Duration::from_secs(var("WS_PING_INTERVAL_SECS", 30)),
+            pong_timeout: Duration::from_secs(var("WS_PONG_TIMEOUT_SECS", 10)),
+        }
+    }
+}
+
+/// 연결 수와 토픽별 구독 수
+pub struct WsHub {
+    pub config: WsConfig,
+    connections: AtomicUsize,
+    users: AtomicUsize,
+    items: AtomicUsize,
+    /// 대기열이 차서 버린 이벤트 수 (누적)
+    dropped: AtomicU64,
+    /// 보내기 시간 초과나 ping 응답이 없어 끊은 연결 수 (누적)
+    timed_out: AtomicU64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WsStats {
+    pub connections: usize,
+    pub max_connections: usize,
+    pub subscribers: WsSubscribers,
+    pub dropped_events: u64,
+    pub timed_out: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct WsSubscribers {
+    pub users: usize,
+    pub items: usize,
+}
+
+impl WsHub {
+    pub fn new(config: WsConfig) -> Self {
+        WsHub {
+            config,
+            connections: AtomicUsize::new(0),
+            users: AtomicUsize::new(0),
+            items: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+            timed_out: AtomicU64::new(0),
+        }
+    }
+
+    pub fn stats(&self) -> WsStats {
+        WsStats {
+            connections: self.connections.load(Ordering::Relaxed),
+            max_connections: self.config.max_connections,
+            subscribers: WsSubscribers {
+                users: self.users.load(Ordering::Relaxed),
+                items: self.items.load(Ordering::Relaxed),
+            },
+            dropped_events: self.dropped.load(Ordering::Relaxed),
+            timed_out: self.timed_out.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 자리가 있으면 연결 하나를 셉니다. 돌려받은 `Connection`을 drop하면 빠집니다.
+    pub fn try_connect(self: &Arc<Self>) -> Option<Connection> {
+        let previous = self.connections.fetch_add(1, Ordering::Relaxed);
+        if previous >= self.config.max_connections {
+            self.connections.fetch_sub(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(Connection { hub: self.clone(), users: false, items: false })
+    }
+
+    fn counter(&self, topic: WsTopic) -> &AtomicUsize {
+        match topic {
+            WsTopic::Users => &self.users,
+            WsTopic::Items => &self.items,
+        }
+    }
+}
+
+/// 연결 하나와 그 연결의 구독 상태. 끊기면(drop) 연결 수와 구독 수를 되돌립니다.
+pub struct Connection {
+    hub: Arc<WsHub>,
+    users: bool,
+    items: bool,
+}
+
+impl Connection {
+    fn subscribed(&mut self, topic: WsTopic) -> &mut bool {
+        match topic {
+            WsTopic::Users => &mut self.users,
+            WsTopic::Items => &mut self.items,
+        }
+    }
+
+    fn set(&mut self, topic: WsTopic, on: bool) {
+        let hub = self.hub.clone();
+        let subscribed = self.subscribed(topic);
+        if *subscribed != on {
+            *subscribed = on;
+            if on {
+                hub.counter(topic).fetch_add(1, Ordering::Relaxed);
+            } else {
+                hub.counter(topic).fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn topics(&self) -> Vec<WsTopic> {
+        [(WsTopic::Users, self.users), (WsTopic::Items, self.items)]
+            .into_iter()
+            .filter_map(|(topic, on)| on.then_some(topic))
+            .collect()
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.set(WsTopic::Users, false);
+        self.set(WsTopic::Items, false);
+        self.hub.connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn text(message: &WsMessage) -> Message {
+    Message::Text(serde_json::to_string(message).unwrap_or_default())
+}
+
+fn close(code: u16, reason: &'static str) -> Message {
+    Message::Close(Some(CloseFrame { code, reason: reason.into() }))
+}
+
+/// 구독하지 않은 토픽은 영원히 기다립니다 (`select!`에서 빠지는 효과).
+async fn recv<T: Clone>(rx: &mut Option<broadcast::Receiver<T>>) -> Result<T, broadcast::error::RecvError> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => pending().await,
+    }
+}
+
+/// 이벤트를 대기열에 넣습니다. 가득 차면 버리고 `missed`를 늘려, 다음에 `lagged`로 알립니다.
+fn forward(hub: &WsHub, out: &mpsc::Sender<Message>, topic: WsTopic, missed: &mut u64, message: WsMessage) {
+    if *missed > 0 {
+        if out.try_send(text(&WsMessage::Lagged { topic, missed: *missed })).is_err() {
+            *missed += 1;
+            hub.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        *missed = 0;
+    }
+    if out.try_send(text(&message)).is_err() {
+        *missed += 1;
+        hub.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// 업그레이드된 연결 하나를 처리합니다. 쓰기(대기열 → 소켓)와 읽기/구독 처리를 같은 task에서 돌립니다.
+pub async fn serve(socket: WebSocket, state: Arc<AppState>, principal: Principal, mut connection: Connection) {
+    let hub = state.websockets.clone();
+    let config = hub.config.clone();
+    let (mut sink, mut stream) = socket.split();
+    let (out, mut queue) = mpsc::channel::<Message>(config.send_queue);
+
+    // 대기열이 닫히거나(읽기 쪽이 끝남) 보내기가 늦으면 끝납니다.
+    let (writer_hub, send_timeout) = (hub.clone(), config.send_timeout);
+    let writer = async move {
+        while let Some(message) = queue.recv().await {
+            match timeout(send_timeout, sink.send(message)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(_)) => break,
+                Err(_) => {
+                    tracing::info!("websocket send timed out, closing slow client");
+                    writer_hub.timed_out.fetch_add(1, Ordering::Relaxed);
+                    break;
+                }
+            }
+        }
+        // 읽기 쪽이 `out.closed()`로 알 수 있게 대기열을 닫습니다.
+        drop(queue);
+        let _ = sink.close().await;
+    };
+
+    let reader = async move {
+        let mut users = None;
+        let mut items = None;
+        let (mut users_missed, mut items_missed) = (0u64, 0u64);
+        let mut ping = interval_at(Instant::now() + config.ping_interval, config.ping_interval);
+        // 답을 받지 못한 첫 ping을 보낸 시각. 아무 프레임이나 받으면 지웁니다.
+        let mut ping_sent: Option<Instant> = None;
+
+        loop {
+            let pong_deadline = ping_sent.map(|sent| sent + config.pong_timeout);
+            tokio::select! {
+                _ = state.shutdown.cancelled() => {
+                    let _ = out.send(close(close_code::AWAY, "server shutting down")).await;
+                    break;
+                }
+                _ = out.closed() => break,
+                _ = ping.tick() => {
+                    if out.send(Message::Ping(Vec::new())).await.is_err() {
+                        break;
+                    }
+                    ping_sent.get_or_insert_with(Instant::now);
+                }
+                // ping이 나가 있을 때만 기다립니다. 다음 tick까지 미루지 않고 WS_PONG_TIMEOUT_SECS에 닫습니다.
+                _ = sleep_until(pong_deadline.unwrap_or_else(Instant::now)), if pong_deadline.is_some() => {
+                    tracing::info!("websocket heartbeat timed out");
+                    hub.timed_out.fetch_add(1, Ordering::Relaxed);
+                    let _ = out.try_send(close(close_code::POLICY, "heartbeat timeout"));
+                    break;
+                }
+                frame = stream.next() => {
+                    let message = match frame {
+                        Some(Ok(message)) => message,
+                        // 상대가 닫았거나 연결 오류
+                        Some(Err(_)) | None => break,
+                    };
+                    ping_sent = None;
+                    let request = match message {
+                        Message::Text(text) => serde_json::from_str::<WsRequest>(&text).map_err(|e| e.to_string()),
+                        Message::Binary(_) => Err("binary messages are not supported".to_string()),
+                        // ping에는 axum이 pong으로 답합니다.
+                        Message::Ping(_) | Message::Pong(_) => continue,
+                        Message::Close(_) => break,
+                    };
+                    let replies = match request {
+                        Ok(WsRequest::Subscribe { topics }) => {
+                            let mut replies = Vec::new();
+                            for topic in topics {
+                                if topic == WsTopic::Users && !principal.has_scope(SCOPE_USERS_READ) {
+                                    replies.push(WsMessage::Error {
+                                        error: format!("topic 'users' requires the '{}' scope", SCOPE_USERS_READ),
+                                    });
+                                    continue;
+                                }
+                                match topic {
+                                    WsTopic::Users if users.is_none() => {
+                                        users = Some(state.user_events.subscribe(None).live);
+                                    }
+                                    WsTopic::Items if items.is_none() => items = Some(state.item_events.subscribe()),
+                                    _ => {}
+                                }
+                                connection.set(topic, true);
+                            }
+                            replies.push(WsMessage::Subscribed { topics: connection.topics() });
+                            replies
+                        }
+                        Ok(WsRequest::Unsubscribe { topics }) => {
+                            for topic in topics {
+                                match topic {
+                                    WsTopic::Users => (users, users_missed) = (None, 0),
+                                    WsTopic::Items => (items, items_missed) = (None, 0),
+                                }
+                                connection.set(topic, false);
+                            }
+                            vec![WsMessage::Subscribed { topics: connection.topics() }]
+                        }
+                        Err(error) => vec![WsMessage::Error { error }],
+                    };
+                    // 요청에 대한 응답은 버리지 않고 자리가 날 때까지 기다립니다.
+                    for reply in replies {
+                        if out.send(text(&reply)).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                event = recv(&mut users) => match event {
+                    Ok(event) => forward(&hub, &out, WsTopic::Users, &mut users_missed, WsMessage::User { event }),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        users_missed += n;
+                        hub.dropped.fetch_add(n, Ordering::Relaxed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => users = None,
+                },
+                event = recv(&mut items) => match event {
+                    Ok(event) => forward(&hub, &out, WsTopic::Items, &mut items_missed, WsMessage::Item { event }),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        items_missed += n;
+                        hub.dropped.fetch_add(n, Ordering::Relaxed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => items = None,
+                },
+            }
+        }
+        // `out`을 drop하면 writer가 남은 메시지(close 포함)를 보내고 끝납니다.
+    };
+
+    tokio::join!(writer, reader);
+}
@@ -1,6 +1,8 @@
 #!/bin/bash
 set -x  # 명령어 실행 전에 명령어 자체를 출력
 
+# DB에 직접 넣으려면: cargo run -- user add "zorba house" zorba@example.com
+
 echo "=== Testing create-user-db ==="
 curl -X POST http://localhost:3000/create-user-db \
   -H "Content-Type: application/json" \
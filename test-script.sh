@@ -42,6 +42,17 @@ echo "=== Testing get_app_state with auth-key ==="
 curl http://localhost:3000/admin/get_app_state -H "X-Admin-API-Key: 2309oijq2309rafjkq230r980afj" | jq
 echo -e "\n"
 
+echo "=== Testing personal access token: create (raw token is shown only once) ==="
+TOKEN=$(curl -s -X POST http://localhost:3000/users/1/tokens \
+  -H "X-Admin-API-Key: 2309oijq2309rafjkq230r980afj" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "test-script", "scopes": ["tokens"]}' | jq -r .token)
+echo -e "\n"
+
+echo "=== Testing personal access token: list with the token itself ==="
+curl http://localhost:3000/users/1/tokens -H "Authorization: Bearer $TOKEN" | jq
+echo -e "\n"
+
 # curl -X POST http://localhost:3000/create-user
 
 # curl http://localhost:3000/users | jq
@@ -55,3 +66,8 @@ echo -e "\n"
 # curl -X DELETE http://localhost:3000/delete-user/2
 
 # curl http://localhost:3000/axum-users | jq
+
+# 압축 응답 / gzip 요청 본문
+# curl --compressed -H "Accept-Encoding: zstd, br, gzip" http://localhost:3000/axum-users | jq
+# echo '{"title": "Some random item"}' | gzip | curl -X POST http://localhost:3000/add-item \
+#      -H "Content-Type: application/json" -H "Content-Encoding: gzip" --data-binary @-
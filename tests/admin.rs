@@ -0,0 +1,61 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn admin_routes_require_admin_key() {
+    let app = test_app().await;
+
+    let res = app.request(get("/admin/get_app_state")).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let mut req = get("/admin/get_app_state");
+    req.headers_mut().insert("X-Admin-API-Key", "wrong".parse().unwrap());
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = app.request(with_admin_key(get("/admin/get_app_state"))).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.request(with_admin_key(get("/admin/slow-events?limit=5"))).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(body_json(res).await.is_array());
+}
+
+#[tokio::test]
+async fn user_token_without_admin_scope_is_forbidden() {
+    let app = test_app().await;
+    app.request(json_request("POST", "/create-user-db", json!({ "name": "alice", "email": "alice@example.com" })))
+        .await;
+    let res = app
+        .request(with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "t", "scopes": ["users:read"] }))))
+        .await;
+    let token = body_json(res).await["token"].as_str().unwrap().to_string();
+
+    let res = app.request(with_bearer(get("/admin/get_app_state"), &token)).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn metrics_exposes_prometheus_text() {
+    let app = test_app().await;
+    app.request(get("/health")).await;
+
+    let res = app.request(get("/metrics")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = body_string(res).await;
+    assert!(body.contains("http_requests_total"));
+    assert!(body.contains("db_pool_connections"));
+}
+
+#[tokio::test]
+async fn metrics_can_require_the_admin_key() {
+    let app = test_app_with(|config| config.metrics.require_admin_key = true).await;
+
+    assert_eq!(app.request(get("/metrics")).await.status(), StatusCode::UNAUTHORIZED);
+    let res = app.request(with_admin_key(get("/metrics"))).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(body_string(res).await.contains("http_requests_total"));
+}
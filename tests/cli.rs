@@ -0,0 +1,84 @@
+// 관리용 CLI 인자 해석
+use axum_rest_api::cli::{ApiVersion, Command};
+use std::path::PathBuf;
+
+fn parse(args: &[&str]) -> Result<Command, String> {
+    Command::parse(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+}
+
+#[test]
+fn defaults_to_serve() {
+    assert_eq!(parse(&[]), Ok(Command::Serve));
+    assert_eq!(parse(&["serve"]), Ok(Command::Serve));
+}
+
+#[test]
+fn parses_commands() {
+    assert_eq!(parse(&["migrate"]), Ok(Command::Migrate));
+    assert_eq!(parse(&["create-admin-key"]), Ok(Command::CreateAdminKey));
+    assert_eq!(parse(&["check-config"]), Ok(Command::CheckConfig));
+    assert_eq!(parse(&["-h"]), Ok(Command::Help));
+    assert_eq!(
+        parse(&["user", "add", "zorba house", "zorba@example.com"]),
+        Ok(Command::UserAdd { name: "zorba house".to_string(), email: "zorba@example.com".to_string() })
+    );
+    assert_eq!(parse(&["user", "list"]), Ok(Command::UserList { json: false }));
+    assert_eq!(parse(&["user", "list", "--json"]), Ok(Command::UserList { json: true }));
+    assert_eq!(parse(&["user", "delete", "42"]), Ok(Command::UserDelete { id: 42 }));
+}
+
+#[test]
+fn parses_openapi_export() {
+    assert_eq!(
+        parse(&["openapi", "export"]),
+        Ok(Command::OpenApiExport { version: ApiVersion::V1, output: None })
+    );
+    assert_eq!(
+        parse(&["openapi", "export", "legacy", "-o", "legacy.json"]),
+        Ok(Command::OpenApiExport { version: ApiVersion::Legacy, output: Some(PathBuf::from("legacy.json")) })
+    );
+    assert_eq!(
+        parse(&["openapi", "export", "--output", "v1.json"]),
+        Ok(Command::OpenApiExport { version: ApiVersion::V1, output: Some(PathBuf::from("v1.json")) })
+    );
+}
+
+#[test]
+fn rejects_unknown_or_incomplete_arguments() {
+    assert!(parse(&["user", "delete", "abc"]).unwrap_err().contains("must be a number"));
+    assert!(parse(&["user", "add", "only-name"]).unwrap_err().starts_with("unrecognized arguments"));
+    assert!(parse(&["user", "list", "--yaml"]).is_err());
+    assert!(parse(&["openapi", "export", "--output"]).is_err());
+    assert!(parse(&["openapi", "export", "v2"]).is_err());
+    assert!(parse(&["serve", "--port", "3000"]).is_err());
+}
+
+/// 서버 바이너리를 깨끗한 환경(.env 없는 임시 디렉터리)에서 실행합니다.
+fn run(args: &[&str], vars: &[(&str, &str)]) -> std::process::Output {
+    let dir = std::env::temp_dir().join(format!("axum-rest-api-cli-{}", uuid::Uuid::now_v7()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_axum-rest-api"))
+        .args(args)
+        .current_dir(&dir)
+        .env_clear()
+        .env("DATABASE_URL", format!("sqlite://{}?mode=rwc", dir.join("cli.db").display()))
+        .env("ADMIN_API_KEY", "0123456789abcdef0123456789abcdef")
+        .envs(vars.iter().copied())
+        .output()
+        .unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    output
+}
+
+#[test]
+fn check_config_reports_invalid_settings_without_panicking() {
+    let output = run(&["check-config"], &[("CORS_MAX_AGE", "abc")]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stdout: {}\nstderr: {}", stdout, stderr);
+    assert!(stdout.contains("FAIL  config       CORS_MAX_AGE must be seconds, got 'abc'"), "{}", stdout);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+
+    let output = run(&["check-config"], &[("PORT", "http")]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("PORT must be a port number"));
+}
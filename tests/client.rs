@@ -0,0 +1,137 @@
+// axum-rest-api-client 를 실제 서버(임의 포트)에 붙여 확인합니다.
+mod common;
+
+use axum_rest_api::openapi::{ApiDoc, ApiDocV1};
+use axum_rest_api_client::models::{BodyItem, CreateTokenRequest, CreateUserRequest};
+use axum_rest_api_client::{Auth, Client, Error, RetryPolicy, OPERATIONS, OPERATIONS_V1};
+use common::*;
+use std::collections::BTreeSet;
+use utoipa::OpenApi;
+
+fn operations(spec: utoipa::openapi::OpenApi) -> BTreeSet<(String, String)> {
+    let spec = serde_json::to_value(spec).unwrap();
+    spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, ops)| ops.as_object().unwrap().keys().map(move |m| (m.clone(), path.clone())))
+        .collect()
+}
+
+fn implemented(operations: &[(&str, &str)]) -> BTreeSet<(String, String)> {
+    operations.iter().map(|(m, p)| (m.to_string(), p.to_string())).collect()
+}
+
+#[test]
+fn client_covers_every_documented_operation() {
+    assert_eq!(operations(ApiDoc::openapi()), implemented(OPERATIONS), "client OPERATIONS are out of sync with ApiDoc");
+    assert_eq!(
+        operations(ApiDocV1::openapi()),
+        implemented(OPERATIONS_V1),
+        "client OPERATIONS_V1 are out of sync with ApiDocV1"
+    );
+}
+
+#[tokio::test]
+async fn client_round_trip() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+    let anonymous = Client::builder(&server.base_url).build().unwrap();
+    let admin = anonymous.with_auth(Auth::AdminKey(ADMIN_KEY.to_string()));
+
+    assert_eq!(anonymous.health().await.unwrap()["status"], "ok");
+    assert_eq!(anonymous.ready().await.unwrap()["status"], "ready");
+    assert_eq!(anonymous.create_user().await.unwrap(), "User Created Successfully");
+    assert_eq!(anonymous.list_users().await.unwrap().len(), 2);
+
+    anonymous
+        .create_user_db(&CreateUserRequest { name: "alice".to_string(), email: "alice@example.com".to_string() })
+        .await
+        .unwrap();
+    let users = anonymous.list_users_db().await.unwrap();
+    assert_eq!(users[0].email, "alice@example.com");
+    assert_eq!(anonymous.delete_user(2).await.unwrap().id, 2);
+
+    assert_eq!(anonymous.show_item(42, 2).await.unwrap(), "Item ID: 42, Page Number: 2");
+    let added = anonymous.add_item(&BodyItem { title: "Some random item".to_string() }).await.unwrap();
+    assert_eq!(added, "Item added: Some random item");
+
+    assert!(admin.get_app_state().await.unwrap().is_object());
+    admin.list_slow_events(Some(5)).await.unwrap();
+
+    // 토큰 발급 -> 토큰으로 인증 -> 폐기
+    let created = admin
+        .create_token(users[0].id, &CreateTokenRequest { name: "ci".to_string(), scopes: vec!["tokens".to_string()] })
+        .await
+        .unwrap();
+    let user = anonymous.with_auth(Auth::Bearer(created.token.clone()));
+    let tokens = user.list_tokens(users[0].id).await.unwrap();
+    assert_eq!(tokens[0].id, created.id);
+    user.revoke_token(users[0].id, created.id).await.unwrap();
+
+    // AppError 본문 디코딩
+    match user.list_tokens(users[0].id).await {
+        Err(Error::Api { status, error }) => {
+            assert_eq!(status, 401);
+            assert!(error.error.starts_with("Unauthorized"));
+            assert!(error.request_id.is_some());
+        }
+        other => panic!("expected 401, got {:?}", other),
+    }
+    let err = anonymous.show_item(1, 2).await.unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(400));
+
+    // OIDC를 설정하지 않았으므로 404
+    let err = anonymous.oidc_login_url().await.unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+    let err = anonymous.oidc_callback("code", "state").await.unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+}
+
+#[tokio::test]
+async fn client_v1_round_trip() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+    let v1 = admin.v1();
+
+    let alice = v1
+        .create_user(&CreateUserRequest { name: "alice".to_string(), email: "alice@example.com".to_string() })
+        .await
+        .unwrap();
+    assert_eq!(v1.get_user(alice.id).await.unwrap().email, "alice@example.com");
+    assert_eq!(v1.list_users().await.unwrap().len(), 1);
+
+    assert_eq!(v1.get_item(42, 2).await.unwrap(), "Item ID: 42, Page Number: 2");
+    assert_eq!(v1.get_item(1, 2).await.unwrap_err().status().map(|s| s.as_u16()), Some(404));
+    assert_eq!(v1.create_item(&BodyItem { title: "lamp".to_string() }).await.unwrap().title, "lamp");
+
+    let created = v1
+        .create_token(alice.id, &CreateTokenRequest { name: "ci".to_string(), scopes: vec!["tokens".to_string()] })
+        .await
+        .unwrap();
+    assert_eq!(v1.list_tokens(alice.id).await.unwrap()[0].id, created.id);
+    v1.revoke_token(alice.id, created.id).await.unwrap();
+
+    v1.delete_user(alice.id).await.unwrap();
+    let err = v1.get_user(alice.id).await.unwrap_err();
+    assert_eq!(err.status().map(|s| s.as_u16()), Some(404));
+}
+
+#[tokio::test]
+async fn connection_errors_are_retried_then_reported() {
+    // 바로 닫은 포트는 연결이 거부됩니다.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let retry = RetryPolicy { max_retries: 2, ..RetryPolicy::default() };
+    let client = Client::builder(format!("http://{}", addr)).retry(retry).build().unwrap();
+    let started = std::time::Instant::now();
+    match client.health().await {
+        Err(Error::Http(e)) => assert!(e.is_connect()),
+        other => panic!("expected a connection error, got {:?}", other),
+    }
+    // 100ms, 200ms(±20%) 두 번 기다린 뒤 포기
+    assert!(started.elapsed() >= std::time::Duration::from_millis(240));
+}
@@ -0,0 +1,87 @@
+// 클라이언트 IP 결정: X-Forwarded-For / Forwarded 해석과 trusted proxy 처리
+use axum::http::HeaderMap;
+use axum_rest_api::middleware::{forwarded_for, x_forwarded_for, ProxyHeader, TrustedProxies};
+use std::net::IpAddr;
+
+fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        headers.append(*name, value.parse().unwrap());
+    }
+    headers
+}
+
+fn ip(s: &str) -> Option<IpAddr> {
+    Some(s.parse().unwrap())
+}
+
+fn proxies(header: ProxyHeader) -> TrustedProxies {
+    TrustedProxies::parse("10.0.0.0/8, 127.0.0.1").unwrap().with_header(header)
+}
+
+#[test]
+fn parses_x_forwarded_for() {
+    let h = headers(&[
+        ("x-forwarded-for", "203.0.113.7, 198.51.100.1:8080"),
+        ("x-forwarded-for", "[2001:db8::1]:4711,unknown, ,2001:db8::2"),
+    ]);
+    assert_eq!(
+        x_forwarded_for(&h),
+        [ip("203.0.113.7"), ip("198.51.100.1"), ip("2001:db8::1"), None, ip("2001:db8::2")]
+    );
+    assert!(x_forwarded_for(&HeaderMap::new()).is_empty());
+}
+
+#[test]
+fn parses_forwarded() {
+    let h = headers(&[
+        ("forwarded", r#"for=192.0.2.60;proto=http;by=203.0.113.43, For="[2001:db8:cafe::17]:4711""#),
+        ("forwarded", "for=_hidden, for=unknown"),
+    ]);
+    assert_eq!(forwarded_for(&h), [ip("192.0.2.60"), ip("2001:db8:cafe::17"), None, None]);
+}
+
+#[test]
+fn forwarded_elements_without_for_still_count_as_hops() {
+    let h = headers(&[("forwarded", "for=203.0.113.7, proto=https;by=10.0.0.2, for=10.0.0.1")]);
+    assert_eq!(forwarded_for(&h), [ip("203.0.113.7"), None, ip("10.0.0.1")]);
+
+    // for= 없는 hop에서 멈추므로 그 왼쪽 주소를 클라이언트로 보지 않습니다.
+    let client = proxies(ProxyHeader::Forwarded).resolve("10.0.0.9".parse().unwrap(), &h);
+    assert_eq!(Some(client), ip("10.0.0.1"));
+}
+
+#[test]
+fn skips_trusted_hops_from_the_right() {
+    let h = headers(&[("x-forwarded-for", "198.51.100.9, 203.0.113.7, 10.1.2.3")]);
+    let client = proxies(ProxyHeader::XForwardedFor).resolve("127.0.0.1".parse().unwrap(), &h);
+    assert_eq!(Some(client), ip("203.0.113.7"));
+
+    // 신뢰하지 않는 peer가 보낸 헤더는 무시합니다.
+    let client = proxies(ProxyHeader::XForwardedFor).resolve("192.0.2.1".parse().unwrap(), &h);
+    assert_eq!(Some(client), ip("192.0.2.1"));
+
+    // IPv4-mapped IPv6 peer도 IPv4로 비교합니다.
+    let client = proxies(ProxyHeader::XForwardedFor).resolve("::ffff:127.0.0.1".parse().unwrap(), &h);
+    assert_eq!(Some(client), ip("203.0.113.7"));
+}
+
+#[test]
+fn reads_only_the_configured_header() {
+    let h = headers(&[("x-forwarded-for", "203.0.113.7"), ("forwarded", "for=198.51.100.9")]);
+    let peer: IpAddr = "10.0.0.1".parse().unwrap();
+    assert_eq!(Some(proxies(ProxyHeader::XForwardedFor).resolve(peer, &h)), ip("203.0.113.7"));
+    assert_eq!(Some(proxies(ProxyHeader::Forwarded).resolve(peer, &h)), ip("198.51.100.9"));
+
+    // 설정한 헤더가 없으면 다른 헤더로 넘어가지 않고 peer를 클라이언트로 봅니다.
+    let only_xff = headers(&[("x-forwarded-for", "203.0.113.7")]);
+    assert_eq!(proxies(ProxyHeader::Forwarded).resolve(peer, &only_xff), peer);
+}
+
+#[test]
+fn parses_proxy_header_setting() {
+    assert_eq!(ProxyHeader::parse("X-Forwarded-For"), Some(ProxyHeader::XForwardedFor));
+    assert_eq!(ProxyHeader::parse(" forwarded "), Some(ProxyHeader::Forwarded));
+    assert_eq!(ProxyHeader::parse("x-real-ip"), None);
+    assert!(TrustedProxies::parse("10.0.0.0/8,not-an-ip").is_err());
+}
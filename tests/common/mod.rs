@@ -0,0 +1,226 @@
+// 통합 테스트 공용 harness
+//
+// 임시 파일 SQLite DB에 migrations/sqlite 를 적용하고 `build_router`로 앱을 만듭니다.
+// - `TestApp::request`: `tower::ServiceExt::oneshot`으로 소켓 없이 요청
+// - `TestApp::spawn`: 임의 포트(127.0.0.1:0)에 `server::serve`로 띄우고 reqwest로 요청
+//
+// 테스트마다 DB 파일이 따로 생기고, `TestApp`이 drop되면 지워집니다.
+#![allow(dead_code)]
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::ConnectInfo,
+    http::{HeaderName, Method, Request, Response},
+    Router,
+};
+use axum_rest_api::{
+    build_router, db, events, metrics, ws, middleware, server, tls, AppConfig, AppState,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+
+pub const ADMIN_KEY: &str = "test-admin-key";
+
+pub struct TestApp {
+    pub config: AppConfig,
+    db_path: PathBuf,
+}
+
+/// 기본 설정으로 앱을 만듭니다. rate limit은 꺼져 있고, 설정은 환경 변수와 관계없이 고정입니다.
+pub async fn test_app() -> TestApp {
+    test_app_with(|_| {}).await
+}
+
+/// `configure`로 라우터를 만들기 전에 설정을 바꿀 수 있습니다 (본문 크기 제한 등).
+pub async fn test_app_with(configure: impl FnOnce(&mut AppConfig)) -> TestApp {
+    let db_path = std::env::temp_dir().join(format!("axum-rest-api-test-{}.db", uuid::Uuid::now_v7()));
+    let db_pool = db::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .expect("failed to open test database");
+    db::run_migrations(&db_pool, &Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations"))
+        .await
+        .expect("failed to run migrations");
+
+    // 환경 변수(.env 등)에 따라 결과가 달라지지 않도록 설정은 모두 여기서 고정합니다.
+    let rate_limit = middleware::RateLimitConfig {
+        enabled: false,
+        default_quota: middleware::Quota { limit: 120, period: Duration::from_secs(60) },
+        route_quotas: HashMap::new(),
+        key_quotas: HashMap::new(),
+    };
+    let ws_config = ws::WsConfig {
+        max_connections: 1000,
+        send_queue: 64,
+        send_timeout: Duration::from_secs(10),
+        ping_interval: Duration::from_secs(30),
+        pong_timeout: Duration::from_secs(10),
+    };
+
+    let app_state = Arc::new(AppState {
+        db_name: "test".to_string(),
+        db_user: String::new(),
+        db_host: String::new(),
+        db_port: String::new(),
+        server_host: "127.0.0.1".to_string(),
+        server_port: "0".to_string(),
+        admin_api_key: ADMIN_KEY.to_string(),
+        oidc: None,
+        rate_limiter: middleware::RateLimiter::new(rate_limit),
+        trusted_proxies: middleware::TrustedProxies::default(),
+        shutdown: CancellationToken::new(),
+        admin_client_cert: tls::ClientCertPolicy::default(),
+        user_events: events::UserEvents::new(events::UserEventsConfig {
+            replay: 256,
+            keep_alive: Duration::from_secs(15),
+        }),
+        item_events: events::ItemEvents::default(),
+        websockets: Arc::new(ws::WsHub::new(ws_config)),
+    });
+
+    let mut config = AppConfig {
+        db_pool,
+        app_state,
+        host: "127.0.0.1".to_string(),
+        port: "0".to_string(),
+        cors: middleware::CorsConfig {
+            allowed_origins: Vec::new(),
+            allowed_methods: vec![Method::GET, Method::POST, Method::DELETE],
+            allowed_headers: ["content-type", "authorization", "x-admin-api-key", "x-request-id"]
+                .map(HeaderName::from_static)
+                .to_vec(),
+            allow_credentials: false,
+            max_age: Duration::from_secs(600),
+        },
+        limits: Arc::new(middleware::LimitsConfig {
+            body_limit: 1024 * 1024,
+            route_body_limits: HashMap::new(),
+            request_timeout: Some(Duration::from_secs(30)),
+            route_timeouts: HashMap::new(),
+            header_read_timeout: Duration::from_secs(10),
+        }),
+        compression: middleware::CompressionConfig {
+            gzip: true,
+            br: true,
+            zstd: true,
+            min_size: 1024,
+            request_decompression: true,
+        },
+        shutdown: server::ShutdownConfig {
+            readiness_delay: Duration::ZERO,
+            drain_timeout: Duration::from_secs(1),
+        },
+        tls: None,
+        deprecation: Arc::new(middleware::DeprecationConfig {
+            deprecated_at: httpdate::parse_http_date("Mon, 19 Oct 2026 00:00:00 GMT").unwrap(),
+            sunset: httpdate::parse_http_date("Mon, 19 Apr 2027 00:00:00 GMT").unwrap(),
+        }),
+        metrics: metrics::MetricsConfig { require_admin_key: false },
+    };
+    configure(&mut config);
+
+    TestApp { config, db_path }
+}
+
+impl TestApp {
+    pub fn router(&self) -> Router {
+        build_router(self.config.clone())
+    }
+
+    /// 소켓 없이 요청 하나를 처리합니다. `server::serve`처럼 `ConnectInfo`를 넣어 줍니다.
+    pub async fn request(&self, mut req: Request<Body>) -> Response<Body> {
+        req.extensions_mut().insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+        self.router().oneshot(req).await.expect("router is infallible")
+    }
+
+    /// 임의 포트에 서버를 띄웁니다. 반환값이 drop되면 서버를 멈춥니다.
+    pub async fn spawn(&self) -> SpawnedApp {
+        self.spawn_with(None).await
+    }
+
+    /// HTTPS로 띄웁니다. 인증서가 localhost용이므로 `base_url`도 https://localhost:<port> 입니다.
+    pub async fn spawn_tls(&self, acceptor: TlsAcceptor) -> SpawnedApp {
+        self.spawn_with(Some(acceptor)).await
+    }
+
+    async fn spawn_with(&self, tls: Option<TlsAcceptor>) -> SpawnedApp {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("failed to bind");
+        let addr = listener.local_addr().unwrap();
+        let base_url = match tls {
+            None => format!("http://{}", addr),
+            Some(_) => format!("https://localhost:{}", addr.port()),
+        };
+        let options = server::ServeOptions {
+            header_read_timeout: self.config.limits.header_read_timeout,
+            drain_timeout: Duration::from_secs(1),
+            tls,
+        };
+        let stop = CancellationToken::new();
+        tokio::spawn(server::serve(listener, self.router(), options, stop.clone().cancelled_owned()));
+
+        SpawnedApp { base_url, stop }
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.db_path.display(), suffix));
+        }
+    }
+}
+
+pub struct SpawnedApp {
+    pub base_url: String,
+    stop: CancellationToken,
+}
+
+impl SpawnedApp {
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+}
+
+impl Drop for SpawnedApp {
+    fn drop(&mut self) {
+        self.stop.cancel();
+    }
+}
+
+pub fn get(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+pub fn json_request(method: &str, uri: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+pub fn with_admin_key(mut req: Request<Body>) -> Request<Body> {
+    req.headers_mut().insert("X-Admin-API-Key", ADMIN_KEY.parse().unwrap());
+    req
+}
+
+pub fn with_bearer(mut req: Request<Body>, token: &str) -> Request<Body> {
+    req.headers_mut()
+        .insert("Authorization", format!("Bearer {}", token).parse().unwrap());
+    req
+}
+
+pub async fn body_string(res: Response<Body>) -> String {
+    let bytes = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+pub async fn body_json(res: Response<Body>) -> serde_json::Value {
+    serde_json::from_str(&body_string(res).await).expect("response is not JSON")
+}
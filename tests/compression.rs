@@ -0,0 +1,68 @@
+// 요청 본문 압축 해제 (REQUEST_DECOMPRESSION)
+use axum::body::{to_bytes, Body, Bytes};
+use axum::http::{header, HeaderMap, Request, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use axum_rest_api::middleware::CompressionConfig;
+use flate2::{write::GzEncoder, Compression};
+use std::io::Write;
+use tower::ServiceExt;
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// 핸들러가 받은 Content-Encoding과 본문을 그대로 돌려줍니다.
+async fn echo(config: &CompressionConfig, encoding: &str, body: Vec<u8>) -> (StatusCode, String, Bytes) {
+    let app = Router::new()
+        .route(
+            "/echo",
+            post(|headers: HeaderMap, body: Bytes| async move {
+                let encoding = headers.get(header::CONTENT_ENCODING).cloned();
+                (encoding.map(|e| [(header::CONTENT_ENCODING, e)]), body)
+            }),
+        )
+        .layer(config.decompression_layer());
+    let req = Request::post("/echo")
+        .header(header::CONTENT_ENCODING, encoding)
+        .body(Body::from(body))
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    let status = res.status();
+    let encoding = res
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    (status, encoding, to_bytes(res.into_body(), usize::MAX).await.unwrap())
+}
+
+fn config(request_decompression: bool) -> CompressionConfig {
+    CompressionConfig { gzip: true, br: true, zstd: true, min_size: 1024, request_decompression }
+}
+
+#[tokio::test]
+async fn decompresses_gzip_request_bodies() {
+    let (status, encoding, body) = echo(&config(true), "gzip", gzip(b"{\"title\":\"lamp\"}")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(encoding, "");
+    assert_eq!(body, "{\"title\":\"lamp\"}");
+
+    let (status, _, _) = echo(&config(true), "br", b"not brotli".to_vec()).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn passes_bodies_through_unchanged_when_disabled() {
+    let compressed = gzip(b"{\"title\":\"lamp\"}");
+    let (status, encoding, body) = echo(&config(false), "gzip", compressed.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(encoding, "gzip");
+    assert_eq!(body, compressed);
+
+    let (status, encoding, body) = echo(&config(false), "br", b"raw".to_vec()).await;
+    assert_eq!((status, encoding.as_str()), (StatusCode::OK, "br"));
+    assert_eq!(body, "raw");
+}
@@ -0,0 +1,375 @@
+// OpenAPI 계약 테스트
+//
+// `ApiDoc`(예전 경로)과 `ApiDocV1`(/api/v1)에 문서화된 operation을 모두 호출하고, 실제 응답이
+// 선언된 상태 코드, content type, 스키마와 맞는지 확인합니다.
+// 새 operation을 문서에 추가하면 아래 `cases`에도 호출을 추가해야 테스트가 통과합니다.
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request};
+use axum_rest_api::openapi::{ApiDoc, ApiDocV1};
+use common::*;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use utoipa::OpenApi;
+
+struct Case {
+    method: &'static str,
+    /// 문서의 경로 템플릿 (예: /users/{id}/tokens)
+    path: &'static str,
+    request: Request<Body>,
+}
+
+fn case(method: &'static str, path: &'static str, request: Request<Body>) -> Case {
+    Case { method, path, request }
+}
+
+fn empty(method: &str, uri: &str) -> Request<Body> {
+    Request::builder().method(method).uri(uri).body(Body::empty()).unwrap()
+}
+
+/// `#/components/schemas/..` 참조를 따라갑니다.
+fn resolve<'a>(spec: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => {
+            let name = reference.strip_prefix("#/components/schemas/").expect("only local schema refs are supported");
+            resolve(spec, &spec["components"]["schemas"][name])
+        }
+        None => schema,
+    }
+}
+
+/// 테스트에 필요한 만큼의 JSON Schema (OpenAPI 3.0 부분집합) 검사
+fn validate(spec: &Value, schema: &Value, value: &Value, at: &str, errors: &mut Vec<String>) {
+    let schema = resolve(spec, schema);
+    if value.is_null() {
+        if schema.get("nullable") != Some(&Value::Bool(true)) && schema.get("type").is_some() {
+            errors.push(format!("{}: null is not allowed", at));
+        }
+        return;
+    }
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        for sub in all_of {
+            validate(spec, sub, value, at, errors);
+        }
+    }
+    if let Some(one_of) = schema.get("oneOf").and_then(Value::as_array) {
+        let matches = one_of
+            .iter()
+            .filter(|sub| {
+                let mut sub_errors = Vec::new();
+                validate(spec, sub, value, at, &mut sub_errors);
+                sub_errors.is_empty()
+            })
+            .count();
+        if matches != 1 {
+            errors.push(format!("{}: expected exactly one oneOf match, got {}", at, matches));
+        }
+    }
+
+    let Some(ty) = schema.get("type").and_then(Value::as_str) else {
+        // 스키마가 비어 있으면(serde_json::Value) 모든 값 허용
+        return;
+    };
+    let type_ok = match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        other => panic!("unsupported schema type '{}'", other),
+    };
+    if !type_ok {
+        errors.push(format!("{}: expected {}, got {}", at, ty, value));
+        return;
+    }
+    if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+        && value.as_f64().is_some_and(|v| v < minimum)
+    {
+        errors.push(format!("{}: {} is below minimum {}", at, value, minimum));
+    }
+
+    match ty {
+        "object" => {
+            let object = value.as_object().unwrap();
+            for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                let name = name.as_str().unwrap();
+                if !object.contains_key(name) {
+                    errors.push(format!("{}: missing required property '{}'", at, name));
+                }
+            }
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, property) in properties {
+                    if let Some(v) = object.get(name) {
+                        validate(spec, property, v, &format!("{}.{}", at, name), errors);
+                    }
+                }
+                // 문서에 없는 필드가 응답에 있으면 문서가 뒤처진 것입니다.
+                for name in object.keys() {
+                    if !properties.contains_key(name) {
+                        errors.push(format!("{}: undocumented property '{}'", at, name));
+                    }
+                }
+            }
+        }
+        "array" => {
+            if let Some(items) = schema.get("items") {
+                for (i, item) in value.as_array().unwrap().iter().enumerate() {
+                    validate(spec, items, item, &format!("{}[{}]", at, i), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 케이스를 차례로 호출해 문서와 비교하고, 문서에 있지만 호출하지 않은 operation도 오류로 모읍니다.
+async fn check(spec: &Value, app: &TestApp, cases: Vec<Case>) -> Vec<String> {
+    let mut covered = BTreeSet::new();
+    let mut errors = Vec::new();
+    for Case { method, path, request } in cases {
+        let uri = request.uri().to_string();
+        let label = format!("{} {}", method.to_uppercase(), uri);
+        let Some(operation) = spec["paths"][path].get(method) else {
+            errors.push(format!("{}: {} {} is not documented", label, method, path));
+            continue;
+        };
+        covered.insert((method, path));
+
+        let res = app.request(request).await;
+        let status = res.status().as_u16().to_string();
+        let Some(response) = operation["responses"].get(&status) else {
+            errors.push(format!("{}: status {} is not documented", label, status));
+            continue;
+        };
+
+        let content_type = res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(';').next().unwrap().trim().to_string());
+        // 이벤트 스트림은 끝나지 않으므로 본문을 읽지 않고 content type만 확인합니다.
+        if content_type.as_deref() == Some("text/event-stream") {
+            if response["content"].get("text/event-stream").is_none() {
+                errors.push(format!("{} -> {}: content-type text/event-stream is not documented", label, status));
+            }
+            continue;
+        }
+        let body = body_string(res).await;
+        let content = response.get("content").and_then(Value::as_object).filter(|c| !c.is_empty());
+
+        match (content, content_type) {
+            (None, _) => {
+                if !body.is_empty() {
+                    errors.push(format!("{} -> {}: body is not documented: {}", label, status, body));
+                }
+            }
+            (Some(_), None) => errors.push(format!("{} -> {}: response has no content-type", label, status)),
+            (Some(content), Some(content_type)) => match content.get(&content_type) {
+                None => errors.push(format!(
+                    "{} -> {}: content-type {} is not documented (documented: {:?})",
+                    label,
+                    status,
+                    content_type,
+                    content.keys().collect::<Vec<_>>()
+                )),
+                Some(media) if content_type == "application/json" => match serde_json::from_str::<Value>(&body) {
+                    Ok(value) => validate(spec, &media["schema"], &value, &format!("{} -> {}", label, status), &mut errors),
+                    Err(e) => errors.push(format!("{} -> {}: invalid JSON body: {}", label, status, e)),
+                },
+                Some(media) => {
+                    let value = Value::String(body);
+                    validate(spec, &media["schema"], &value, &format!("{} -> {}", label, status), &mut errors);
+                }
+            },
+        }
+    }
+
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        for method in operations.as_object().unwrap().keys() {
+            if !covered.iter().any(|(m, p)| m == method && p == path) {
+                errors.push(format!("{} {} is documented but not exercised by this test", method.to_uppercase(), path));
+            }
+        }
+    }
+
+    errors
+}
+
+#[tokio::test]
+async fn responses_match_openapi_document() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let app = test_app().await;
+
+    // 토큰 테스트용 사용자와 토큰
+    let res = app
+        .request(json_request("POST", "/create-user-db", json!({ "name": "alice", "email": "alice@example.com" })))
+        .await;
+    assert_eq!(res.status(), 201);
+    let res = app
+        .request(with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "reader", "scopes": ["users:read"] }))))
+        .await;
+    let reader_token = body_json(res).await["token"].as_str().unwrap().to_string();
+    // slow-events 응답 스키마도 확인할 수 있도록 항목을 하나 남깁니다.
+    axum_rest_api::metrics::slow::SLOW_EVENTS.record_request("GET", "/contract", std::time::Duration::from_secs(60));
+
+    let cases = vec![
+        case("get", "/health", get("/health")),
+        case("get", "/ready", get("/ready")),
+        case("post", "/create-user", empty("POST", "/create-user")),
+        case("post", "/create-user-db", json_request("POST", "/create-user-db", json!({ "name": "bob", "email": "bob@example.com" }))),
+        case("get", "/users", get("/users")),
+        case("get", "/axum-users", get("/axum-users")),
+        case("delete", "/delete-user/{id}", empty("DELETE", "/delete-user/2")),
+        case("delete", "/delete-user/{id}", empty("DELETE", "/delete-user/1")),
+        case("get", "/item/{id}", get("/item/42?number=2")),
+        case("get", "/item/{id}", get("/item/42")),
+        case("get", "/item/{id}", get("/item/1?number=2")),
+        case("post", "/add-item", json_request("POST", "/add-item", json!({ "title": "Some random item" }))),
+        case("get", "/admin/get_app_state", with_admin_key(get("/admin/get_app_state"))),
+        case("get", "/admin/get_app_state", get("/admin/get_app_state")),
+        case("get", "/admin/get_app_state", with_bearer(get("/admin/get_app_state"), &reader_token)),
+        case("get", "/admin/slow-events", with_admin_key(get("/admin/slow-events?limit=5"))),
+        case("get", "/admin/slow-events", get("/admin/slow-events")),
+        case("get", "/admin/slow-events", with_bearer(get("/admin/slow-events"), &reader_token)),
+        case(
+            "post",
+            "/users/{id}/tokens",
+            with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] }))),
+        ),
+        case(
+            "post",
+            "/users/{id}/tokens",
+            with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "ci", "scopes": ["nope"] }))),
+        ),
+        case("post", "/users/{id}/tokens", json_request("POST", "/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] }))),
+        case(
+            "post",
+            "/users/{id}/tokens",
+            with_bearer(json_request("POST", "/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] })), &reader_token),
+        ),
+        case("get", "/users/{id}/tokens", with_admin_key(get("/users/1/tokens"))),
+        case("get", "/users/{id}/tokens", get("/users/1/tokens")),
+        case("get", "/users/{id}/tokens", with_bearer(get("/users/2/tokens"), &reader_token)),
+        case("delete", "/users/{id}/tokens/{token_id}", with_admin_key(empty("DELETE", "/users/1/tokens/2"))),
+        case("delete", "/users/{id}/tokens/{token_id}", with_admin_key(empty("DELETE", "/users/1/tokens/2"))),
+        case("delete", "/users/{id}/tokens/{token_id}", empty("DELETE", "/users/1/tokens/1")),
+        case("delete", "/users/{id}/tokens/{token_id}", with_bearer(empty("DELETE", "/users/2/tokens/1"), &reader_token)),
+        // OIDC는 설정하지 않았으므로 404 (IdP가 필요한 경로는 여기서 다루지 않습니다)
+        case("get", "/auth/oidc/login", get("/auth/oidc/login")),
+        case("get", "/auth/oidc/callback", get("/auth/oidc/callback?code=x&state=y")),
+        // 업그레이드는 tests/websocket.rs에서 확인합니다. 여기서는 업그레이드가 아닌 요청(400)과 인증 실패
+        case("get", "/ws", with_admin_key(get("/ws"))),
+        case("get", "/ws", get("/ws")),
+    ];
+
+    let errors = check(&spec, &app, cases).await;
+    assert!(errors.is_empty(), "OpenAPI contract violations:\n  {}", errors.join("\n  "));
+}
+
+#[tokio::test]
+async fn v1_responses_match_openapi_document() {
+    let spec = serde_json::to_value(ApiDocV1::openapi()).unwrap();
+    let app = test_app().await;
+
+    let res = app
+        .request(with_admin_key(json_request("POST", "/api/v1/users", json!({ "name": "alice", "email": "alice@example.com" }))))
+        .await;
+    assert_eq!(res.status(), 201);
+    let res = app
+        .request(with_admin_key(json_request("POST", "/api/v1/users/1/tokens", json!({ "name": "reader", "scopes": ["users:read"] }))))
+        .await;
+    let reader_token = body_json(res).await["token"].as_str().unwrap().to_string();
+    axum_rest_api::metrics::slow::SLOW_EVENTS.record_request("GET", "/contract", std::time::Duration::from_secs(60));
+
+    let cases = vec![
+        case("get", "/health", get("/health")),
+        case("get", "/ready", get("/ready")),
+        case(
+            "post",
+            "/api/v1/users",
+            with_admin_key(json_request("POST", "/api/v1/users", json!({ "name": "bob", "email": "bob@example.com" }))),
+        ),
+        case("post", "/api/v1/users", json_request("POST", "/api/v1/users", json!({ "name": "eve", "email": "eve@example.com" }))),
+        case(
+            "post",
+            "/api/v1/users",
+            with_bearer(json_request("POST", "/api/v1/users", json!({ "name": "eve", "email": "eve@example.com" })), &reader_token),
+        ),
+        case("get", "/api/v1/users", get("/api/v1/users")),
+        case("get", "/api/v1/users/{id}", get("/api/v1/users/2")),
+        case("get", "/api/v1/users/{id}", get("/api/v1/users/99")),
+        case("patch", "/api/v1/users/{id}", with_admin_key(json_request("PATCH", "/api/v1/users/2", json!({ "name": "robert" })))),
+        case("patch", "/api/v1/users/{id}", with_admin_key(json_request("PATCH", "/api/v1/users/99", json!({ "name": "robert" })))),
+        case("patch", "/api/v1/users/{id}", json_request("PATCH", "/api/v1/users/2", json!({ "name": "mallory" }))),
+        case(
+            "patch",
+            "/api/v1/users/{id}",
+            with_bearer(json_request("PATCH", "/api/v1/users/2", json!({ "name": "mallory" })), &reader_token),
+        ),
+        case("delete", "/api/v1/users/{id}", empty("DELETE", "/api/v1/users/2")),
+        case("delete", "/api/v1/users/{id}", with_bearer(empty("DELETE", "/api/v1/users/2"), &reader_token)),
+        case("delete", "/api/v1/users/{id}", with_admin_key(empty("DELETE", "/api/v1/users/2"))),
+        case("delete", "/api/v1/users/{id}", with_admin_key(empty("DELETE", "/api/v1/users/2"))),
+        case("get", "/api/v1/users/events", with_bearer(get("/api/v1/users/events"), &reader_token)),
+        case("get", "/api/v1/users/events", get("/api/v1/users/events")),
+        case("get", "/api/v1/items/{id}", get("/api/v1/items/42?number=2")),
+        case("get", "/api/v1/items/{id}", get("/api/v1/items/42")),
+        case("get", "/api/v1/items/{id}", get("/api/v1/items/1?number=2")),
+        case("post", "/api/v1/items", json_request("POST", "/api/v1/items", json!({ "title": "Some random item" }))),
+        case("get", "/admin/get_app_state", with_admin_key(get("/admin/get_app_state"))),
+        case("get", "/admin/get_app_state", get("/admin/get_app_state")),
+        case("get", "/admin/get_app_state", with_bearer(get("/admin/get_app_state"), &reader_token)),
+        case("get", "/admin/slow-events", with_admin_key(get("/admin/slow-events?limit=5"))),
+        case("get", "/admin/slow-events", get("/admin/slow-events")),
+        case("get", "/admin/slow-events", with_bearer(get("/admin/slow-events"), &reader_token)),
+        case(
+            "post",
+            "/api/v1/users/{id}/tokens",
+            with_admin_key(json_request("POST", "/api/v1/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] }))),
+        ),
+        case(
+            "post",
+            "/api/v1/users/{id}/tokens",
+            with_admin_key(json_request("POST", "/api/v1/users/1/tokens", json!({ "name": "ci", "scopes": ["nope"] }))),
+        ),
+        case("post", "/api/v1/users/{id}/tokens", json_request("POST", "/api/v1/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] }))),
+        case(
+            "post",
+            "/api/v1/users/{id}/tokens",
+            with_bearer(json_request("POST", "/api/v1/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] })), &reader_token),
+        ),
+        case("get", "/api/v1/users/{id}/tokens", with_admin_key(get("/api/v1/users/1/tokens"))),
+        case("get", "/api/v1/users/{id}/tokens", get("/api/v1/users/1/tokens")),
+        case("get", "/api/v1/users/{id}/tokens", with_bearer(get("/api/v1/users/2/tokens"), &reader_token)),
+        case("delete", "/api/v1/users/{id}/tokens/{token_id}", with_admin_key(empty("DELETE", "/api/v1/users/1/tokens/2"))),
+        case("delete", "/api/v1/users/{id}/tokens/{token_id}", with_admin_key(empty("DELETE", "/api/v1/users/1/tokens/2"))),
+        case("delete", "/api/v1/users/{id}/tokens/{token_id}", empty("DELETE", "/api/v1/users/1/tokens/1")),
+        case("delete", "/api/v1/users/{id}/tokens/{token_id}", with_bearer(empty("DELETE", "/api/v1/users/2/tokens/1"), &reader_token)),
+        case("get", "/auth/oidc/login", get("/auth/oidc/login")),
+        case("get", "/auth/oidc/callback", get("/auth/oidc/callback?code=x&state=y")),
+        // 업그레이드는 tests/websocket.rs에서 확인합니다. 여기서는 업그레이드가 아닌 요청(400)과 인증 실패
+        case("get", "/ws", with_admin_key(get("/ws"))),
+        case("get", "/ws", get("/ws")),
+    ];
+
+    let errors = check(&spec, &app, cases).await;
+    assert!(errors.is_empty(), "OpenAPI contract violations:\n  {}", errors.join("\n  "));
+}
+
+#[test]
+fn legacy_operations_are_marked_deprecated() {
+    let legacy = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    assert_eq!(legacy["paths"]["/delete-user/{id}"]["delete"]["deprecated"], true);
+    assert_eq!(legacy["paths"]["/users/{id}/tokens/{token_id}"]["delete"]["deprecated"], true);
+    assert!(legacy["paths"]["/health"]["get"].get("deprecated").is_none());
+
+    let v1 = serde_json::to_value(ApiDocV1::openapi()).unwrap();
+    for (path, operations) in v1["paths"].as_object().unwrap() {
+        assert!(!path.starts_with("/users") && !path.starts_with("/item"), "legacy path {} in the v1 document", path);
+        for (method, operation) in operations.as_object().unwrap() {
+            assert!(operation.get("deprecated").is_none(), "{} {} is deprecated in v1", method, path);
+        }
+    }
+}
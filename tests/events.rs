@@ -0,0 +1,225 @@
+// GET /api/v1/users/events (Server-Sent Events)
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum_rest_api::events::{UserEvents, UserEventsConfig};
+use axum_rest_api::models::{CreateUserRequest, UpdateUserRequest, UserChange, UserEvent};
+use axum_rest_api_client::{Client, StreamEvent};
+use common::*;
+use http_body_util::BodyExt;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// SSE 본문을 빈 줄로 끝나는 블록 단위로 읽습니다.
+struct EventReader {
+    body: Body,
+    buffer: String,
+}
+
+impl EventReader {
+    /// 다음 블록. 스트림이 끝나면 None
+    async fn next_block(&mut self) -> Option<String> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let block = self.buffer[..end].to_string();
+                self.buffer.drain(..end + 2);
+                return Some(block);
+            }
+            let frame = tokio::time::timeout(Duration::from_secs(5), self.body.frame())
+                .await
+                .expect("no event within 5 seconds")?
+                .unwrap();
+            if let Ok(data) = frame.into_data() {
+                self.buffer.push_str(std::str::from_utf8(&data).unwrap());
+            }
+        }
+    }
+
+    /// 다음 이벤트 (keep-alive 주석은 건너뜀): (id, event, data)
+    async fn next_event(&mut self) -> (String, String, String) {
+        loop {
+            let block = self.next_block().await.expect("stream ended");
+            let field = |name: &str| {
+                block
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name).map(str::to_string))
+                    .unwrap_or_default()
+            };
+            if block.lines().all(|line| line.starts_with(':')) {
+                continue;
+            }
+            return (field("id: "), field("event: "), field("data: "));
+        }
+    }
+
+    async fn next_user_event(&mut self) -> UserEvent {
+        let (id, _, data) = self.next_event().await;
+        let event: UserEvent = serde_json::from_str(&data).unwrap();
+        assert_eq!(id, event.id.to_string());
+        event
+    }
+}
+
+async fn subscribe(app: &TestApp, last_event_id: Option<u64>) -> EventReader {
+    let mut req = Request::get("/api/v1/users/events");
+    if let Some(id) = last_event_id {
+        req = req.header("last-event-id", id.to_string());
+    }
+    let res = app.request(with_admin_key(req.body(Body::empty()).unwrap())).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "text/event-stream");
+    EventReader { body: res.into_body(), buffer: String::new() }
+}
+
+async fn create_user(app: &TestApp, name: &str) -> i64 {
+    let res = app
+        .request(with_admin_key(json_request("POST", "/api/v1/users", json!({ "name": name, "email": format!("{}@example.com", name) }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    body_json(res).await["id"].as_i64().unwrap()
+}
+
+fn with_user_events(config: UserEventsConfig) -> impl FnOnce(&mut axum_rest_api::AppConfig) {
+    move |app_config| {
+        Arc::get_mut(&mut app_config.app_state).unwrap().user_events = UserEvents::new(config);
+    }
+}
+
+#[tokio::test]
+async fn streams_create_update_and_delete() {
+    let app = test_app().await;
+    let mut events = subscribe(&app, None).await;
+
+    let id = create_user(&app, "alice").await;
+    let res = app
+        .request(with_admin_key(json_request("PATCH", &format!("/api/v1/users/{}", id), json!({ "email": "a@example.com" }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let req = Request::delete(format!("/api/v1/users/{}", id)).body(Body::empty()).unwrap();
+    assert_eq!(app.request(with_admin_key(req)).await.status(), StatusCode::NO_CONTENT);
+    // 예전 경로로 만든 사용자도 알립니다.
+    app.request(json_request("POST", "/create-user-db", json!({ "name": "bob", "email": "bob@example.com" }))).await;
+
+    let created = events.next_user_event().await;
+    assert_eq!(created.change, UserChange::Created);
+    assert_eq!(created.user.name, "alice");
+
+    let (_, name, _) = events.next_event().await;
+    assert_eq!(name, "updated");
+
+    let deleted = events.next_user_event().await;
+    assert_eq!(deleted.change, UserChange::Deleted);
+    assert_eq!(deleted.user.email, "a@example.com");
+    assert_eq!(deleted.id, created.id + 2);
+
+    assert_eq!(events.next_user_event().await.user.name, "bob");
+}
+
+#[tokio::test]
+async fn requires_the_users_read_scope() {
+    let app = test_app().await;
+    let id = create_user(&app, "alice").await;
+    let token = |scope: &str| {
+        let uri = format!("/api/v1/users/{}/tokens", id);
+        let req = with_admin_key(json_request("POST", &uri, json!({ "name": scope, "scopes": [scope] })));
+        async { body_json(app.request(req).await).await["token"].as_str().unwrap().to_string() }
+    };
+
+    assert_eq!(app.request(get("/api/v1/users/events")).await.status(), StatusCode::UNAUTHORIZED);
+    let res = app.request(with_bearer(get("/api/v1/users/events"), &token("tokens").await)).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let res = app.request(with_bearer(get("/api/v1/users/events"), &token("users:read").await)).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "text/event-stream");
+}
+
+#[tokio::test]
+async fn resumes_from_last_event_id() {
+    let app = test_app().await;
+    let mut first = subscribe(&app, None).await;
+    for name in ["a", "b", "c"] {
+        create_user(&app, name).await;
+    }
+    let a = first.next_user_event().await;
+
+    let mut resumed = subscribe(&app, Some(a.id)).await;
+    assert_eq!(resumed.next_user_event().await.user.name, "b");
+    assert_eq!(resumed.next_user_event().await.user.name, "c");
+    // 버퍼 다음은 실시간 이벤트
+    create_user(&app, "d").await;
+    let d = resumed.next_user_event().await;
+    assert_eq!((d.user.name.as_str(), d.id), ("d", a.id + 3));
+}
+
+#[tokio::test]
+async fn resync_when_the_replay_buffer_no_longer_has_the_events() {
+    let app = test_app_with(with_user_events(UserEventsConfig { replay: 2, keep_alive: Duration::from_secs(15) })).await;
+    let mut first = subscribe(&app, None).await;
+    for name in ["a", "b", "c", "d"] {
+        create_user(&app, name).await;
+    }
+    let a = first.next_user_event().await;
+
+    // b는 버퍼에서 밀려났으므로 목록을 다시 읽으라는 resync를 받습니다.
+    let mut resumed = subscribe(&app, Some(a.id)).await;
+    let (id, name, data) = resumed.next_event().await;
+    assert_eq!(name, "resync");
+    assert_eq!(id, (a.id + 3).to_string());
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&data).unwrap()["last_id"], a.id + 3);
+
+    // 재시작 전 서버의 id(훨씬 작은 값)나 미래의 id도 resync
+    for stale in [1, a.id + 100] {
+        let mut reader = subscribe(&app, Some(stale)).await;
+        assert_eq!(reader.next_event().await.1, "resync");
+    }
+
+    // 버퍼에 남은 구간은 그대로 이어서 받습니다.
+    let mut resumed = subscribe(&app, Some(a.id + 2)).await;
+    assert_eq!(resumed.next_user_event().await.user.name, "d");
+}
+
+#[tokio::test]
+async fn keep_alive_comments_and_shutdown() {
+    let app = test_app_with(with_user_events(UserEventsConfig { replay: 16, keep_alive: Duration::from_secs(1) })).await;
+    let mut events = subscribe(&app, None).await;
+
+    let block = events.next_block().await.unwrap();
+    assert_eq!(block, ": keep-alive");
+
+    // 종료 신호를 받으면 스트림이 끝납니다.
+    app.config.app_state.shutdown.cancel();
+    while let Some(block) = events.next_block().await {
+        assert!(block.starts_with(':'), "unexpected block after shutdown: {}", block);
+    }
+}
+
+#[tokio::test]
+async fn client_follows_and_resumes_the_stream() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+    let client = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+    let v1 = client.v1();
+
+    let mut stream = v1.user_events(None).await.unwrap();
+    let alice = v1
+        .create_user(&CreateUserRequest { name: "alice".to_string(), email: "alice@example.com".to_string() })
+        .await
+        .unwrap();
+    let changes = UpdateUserRequest { name: Some("alicia".to_string()), ..Default::default() };
+    assert_eq!(v1.update_user(alice.id, &changes).await.unwrap().name, "alicia");
+
+    let Some(StreamEvent::User(created)) = stream.next().await.unwrap() else { panic!("expected a user event") };
+    assert_eq!((created.change, created.user.id), (UserChange::Created, alice.id));
+    drop(stream);
+
+    // 끊긴 뒤의 변경은 Last-Event-ID로 이어 받습니다.
+    let mut resumed = v1.user_events(Some(created.id)).await.unwrap();
+    let Some(StreamEvent::User(updated)) = resumed.next().await.unwrap() else { panic!("expected a user event") };
+    assert_eq!((updated.change, updated.user.name.as_str()), (UserChange::Updated, "alicia"));
+    assert_eq!(resumed.last_event_id(), Some(updated.id));
+
+    let mut stale = v1.user_events(Some(1)).await.unwrap();
+    assert!(matches!(stale.next().await.unwrap(), Some(StreamEvent::Resync { last_id }) if last_id == updated.id));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum_rest_api::server;
+use common::*;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test]
+async fn health_and_ready() {
+    let app = test_app().await;
+
+    let res = app.request(get("/health")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.request(get("/ready")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_json(res).await["status"], "ready");
+}
+
+#[tokio::test]
+async fn ready_reports_draining_after_shutdown() {
+    let app = test_app().await;
+    app.config.app_state.shutdown.cancel();
+
+    let res = app.request(get("/ready")).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body_json(res).await["status"], "draining");
+}
+
+#[tokio::test]
+async fn shutdown_keeps_serving_for_the_readiness_delay() {
+    if std::env::var("SHUTDOWN_READINESS_DELAY_SECS").is_err() {
+        assert_eq!(server::ShutdownConfig::from_env().unwrap().readiness_delay, Duration::from_secs(5));
+    }
+
+    let token = CancellationToken::new();
+    let config = server::ShutdownConfig { readiness_delay: Duration::from_millis(300), drain_timeout: Duration::ZERO };
+    let signal = tokio::spawn(server::shutdown_signal(token.clone(), config));
+    let start = Instant::now();
+    token.cancel();
+    signal.await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn serves_over_tcp() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+
+    let res = reqwest::get(server.url("/")).await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+    assert_eq!(res.text().await.unwrap(), "hello, Rust!");
+
+    let res = reqwest::get(server.url("/api-docs/openapi.json")).await.unwrap();
+    assert_eq!(res.status(), reqwest::StatusCode::OK);
+    let doc: serde_json::Value = res.json().await.unwrap();
+    assert!(doc["paths"]["/users/{id}/tokens"].is_object());
+}
+
+#[tokio::test]
+async fn unknown_route_is_404() {
+    let app = test_app().await;
+    let res = app.request(get("/no-such-route")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
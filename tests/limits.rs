@@ -0,0 +1,28 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn body_over_limit_is_413() {
+    let app = test_app_with(|config| {
+        let mut limits = (*config.limits).clone();
+        limits.body_limit = 64;
+        config.limits = std::sync::Arc::new(limits);
+    })
+    .await;
+
+    let res = app.request(json_request("POST", "/add-item", json!({ "title": "short" }))).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.request(json_request("POST", "/add-item", json!({ "title": "x".repeat(100) }))).await;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn responses_carry_request_id() {
+    let app = test_app().await;
+    let res = app.request(get("/health")).await;
+    assert!(res.headers().contains_key("x-request-id"));
+}
@@ -0,0 +1,135 @@
+// Accept / Content-Type 내용 협상 (JSON, MessagePack, CBOR)
+mod common;
+
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum_rest_api::models::{CreateUserRequest, Format, User};
+use axum_rest_api::AcceptFormat;
+use axum_rest_api_client::{Client, Error};
+use common::*;
+use serde_json::json;
+
+fn accept(uri: &str, accept: &str) -> Request<Body> {
+    Request::get(uri).header(header::ACCEPT, accept).body(Body::empty()).unwrap()
+}
+
+/// 관리자 키를 붙인 POST. /api/v1/users 생성은 인증이 필요합니다.
+fn encoded(uri: &str, format: Format, value: &impl serde::Serialize, accept: &str) -> Request<Body> {
+    Request::post(uri)
+        .header("X-Admin-API-Key", ADMIN_KEY)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(header::ACCEPT, accept)
+        .body(Body::from(format.encode(value).unwrap()))
+        .unwrap()
+}
+
+fn content_type(res: &axum::http::Response<Body>) -> &str {
+    res.headers()[header::CONTENT_TYPE].to_str().unwrap()
+}
+
+async fn body_bytes(res: axum::http::Response<Body>) -> Vec<u8> {
+    axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap().to_vec()
+}
+
+#[tokio::test]
+async fn user_lists_in_every_format() {
+    let app = test_app().await;
+    let alice = CreateUserRequest { name: "alice".to_string(), email: "alice@example.com".to_string() };
+    app.request(encoded("/api/v1/users", Format::Json, &alice, "application/json")).await;
+
+    for format in Format::ALL {
+        let res = app.request(accept("/api/v1/users", format.content_type())).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(content_type(&res).split(';').next().unwrap(), format.content_type());
+        assert!(res.headers().get_all(header::VARY).iter().any(|v| v == "accept"));
+        let users: Vec<User> = format.decode(&body_bytes(res).await).unwrap();
+        assert_eq!(users[0].email, "alice@example.com");
+    }
+
+    // 예전 경로도 같은 모델을 돌려주므로 협상합니다.
+    let res = app.request(accept("/axum-users", "application/cbor")).await;
+    assert_eq!(content_type(&res), "application/cbor");
+}
+
+#[tokio::test]
+async fn binary_request_bodies() {
+    let app = test_app().await;
+    let bob = CreateUserRequest { name: "bob".to_string(), email: "bob@example.com".to_string() };
+
+    let res = app.request(encoded("/api/v1/users", Format::MsgPack, &bob, "application/cbor")).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(content_type(&res), "application/cbor");
+    let user: User = Format::Cbor.decode(&body_bytes(res).await).unwrap();
+    assert_eq!(user.name, "bob");
+
+    let res = app.request(encoded("/create-user-db", Format::Cbor, &bob, "*/*")).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    // 디코딩 실패는 JSON과 같이 422, 모르는 Content-Type은 415 (ErrorResponse)
+    let res = app.request(encoded("/api/v1/users", Format::MsgPack, &json!({ "name": "x" }), "application/json")).await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let req = Request::post("/api/v1/users").header(header::CONTENT_TYPE, "text/plain").body(Body::from("bob")).unwrap();
+    let res = app.request(with_admin_key(req)).await;
+    assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert!(body_json(res).await["error"].as_str().unwrap().contains("application/msgpack"));
+}
+
+#[tokio::test]
+async fn unsupported_accept_is_406_before_the_handler_runs() {
+    let app = test_app().await;
+
+    let res = app.request(accept("/api/v1/users", "text/html")).await;
+    assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(content_type(&res), "application/json");
+
+    let bob = CreateUserRequest { name: "bob".to_string(), email: "bob@example.com".to_string() };
+    let res = app.request(encoded("/api/v1/users", Format::Json, &bob, "application/xml")).await;
+    assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+    let users = body_json(app.request(get("/api/v1/users")).await).await;
+    assert_eq!(users, json!([]));
+
+    // 텍스트를 돌려주는 경로는 협상하지 않습니다.
+    let res = app.request(accept("/api/v1/items/42?number=2", "application/cbor")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[test]
+fn accept_header_preferences() {
+    assert_eq!(AcceptFormat::from_accept("application/msgpack"), Some(Format::MsgPack));
+    assert_eq!(AcceptFormat::from_accept("application/x-msgpack"), Some(Format::MsgPack));
+    assert_eq!(AcceptFormat::from_accept("application/json;q=0.5, application/cbor"), Some(Format::Cbor));
+    assert_eq!(AcceptFormat::from_accept("application/cbor;q=0.2, application/json;q=0.9"), Some(Format::Json));
+    // 같은 q면 와일드카드보다 구체적인 형식
+    assert_eq!(AcceptFormat::from_accept("*/*, application/msgpack"), Some(Format::MsgPack));
+    assert_eq!(AcceptFormat::from_accept("text/html, */*;q=0.8"), Some(Format::Json));
+    assert_eq!(AcceptFormat::from_accept("application/cbor;q=0, text/html"), None);
+    assert_eq!(AcceptFormat::from_accept("image/png"), None);
+}
+
+#[tokio::test]
+async fn client_speaks_msgpack_and_cbor() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+
+    for format in [Format::MsgPack, Format::Cbor] {
+        let client = Client::builder(&server.base_url).admin_key(ADMIN_KEY).format(format).build().unwrap();
+        let email = format!("{:?}@example.com", format).to_lowercase();
+        let user = client
+            .v1()
+            .create_user(&CreateUserRequest { name: "carol".to_string(), email: email.clone() })
+            .await
+            .unwrap();
+        assert_eq!(client.v1().get_user(user.id).await.unwrap().email, email);
+        // 협상하지 않는 경로는 JSON으로 받습니다.
+        assert_eq!(client.health().await.unwrap()["status"], "ok");
+        assert!(client.get_app_state().await.unwrap().is_object());
+
+        match client.v1().get_user(999).await {
+            Err(Error::Api { status, error }) => {
+                assert_eq!(status, 404);
+                assert!(error.error.starts_with("User not found"));
+            }
+            other => panic!("expected 404, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+// OIDC 로그인 (authorization code + PKCE)을 테스트용 IdP로 확인합니다.
+// IdP는 discovery, JWKS, token endpoint만 흉내 내고, authorization endpoint 대신 테스트가 code를 직접 등록합니다.
+mod common;
+
+use axum::extract::{Form, State};
+use axum::http::{header, StatusCode};
+use axum::routing::{get as get_route, post};
+use axum::{Json, Router};
+use axum_rest_api::auth::oidc::{pkce_challenge, OidcClient, OidcConfig};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use common::*;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const CLIENT_ID: &str = "axum-rest-api";
+
+struct Grant {
+    claims: Value,
+    code_challenge: String,
+}
+
+struct MockIdp {
+    issuer: String,
+    pkcs8: Vec<u8>,
+    public_key: Vec<u8>,
+    grants: Mutex<HashMap<String, Grant>>,
+}
+
+async fn discovery(State(idp): State<Arc<MockIdp>>) -> Json<Value> {
+    Json(json!({
+        "issuer": idp.issuer,
+        "authorization_endpoint": format!("{}/authorize", idp.issuer),
+        "token_endpoint": format!("{}/token", idp.issuer),
+        "jwks_uri": format!("{}/jwks", idp.issuer),
+    }))
+}
+
+async fn jwks(State(idp): State<Arc<MockIdp>>) -> Json<Value> {
+    Json(json!({
+        "keys": [{
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "kid": "test-key",
+            "alg": "EdDSA",
+            "use": "sig",
+            "x": URL_SAFE_NO_PAD.encode(&idp.public_key),
+        }]
+    }))
+}
+
+async fn token(
+    State(idp): State<Arc<MockIdp>>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let invalid = |error: &str| (StatusCode::BAD_REQUEST, Json(json!({ "error": error })));
+    let grant = idp.grants.lock().unwrap().remove(&form["code"]).ok_or_else(|| invalid("invalid_grant"))?;
+    if pkce_challenge(&form["code_verifier"]) != grant.code_challenge || form["client_id"] != CLIENT_ID {
+        return Err(invalid("invalid_grant"));
+    }
+
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some("test-key".to_string());
+    let id_token = jsonwebtoken::encode(&header, &grant.claims, &EncodingKey::from_ed_der(&idp.pkcs8)).unwrap();
+    Ok(Json(json!({ "access_token": "unused", "token_type": "Bearer", "id_token": id_token })))
+}
+
+async fn start_idp() -> Arc<MockIdp> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap().as_ref().to_vec();
+    let public_key = Ed25519KeyPair::from_pkcs8(&pkcs8).unwrap().public_key().as_ref().to_vec();
+    let idp = Arc::new(MockIdp {
+        issuer: format!("http://{}", listener.local_addr().unwrap()),
+        pkcs8,
+        public_key,
+        grants: Mutex::new(HashMap::new()),
+    });
+
+    let router = Router::new()
+        .route("/.well-known/openid-configuration", get_route(discovery))
+        .route("/jwks", get_route(jwks))
+        .route("/token", post(token))
+        .with_state(idp.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    idp
+}
+
+async fn oidc_app(idp: &MockIdp, configure: impl FnOnce(&mut OidcConfig)) -> TestApp {
+    let mut config = OidcConfig {
+        discovery_url: format!("{}/.well-known/openid-configuration", idp.issuer),
+        client_id: CLIENT_ID.to_string(),
+        client_secret: "secret".to_string(),
+        redirect_url: "http://localhost/auth/oidc/callback".to_string(),
+        scopes: vec!["openid".to_string(), "email".to_string()],
+        token_scopes: vec!["tokens".to_string()],
+        link_verified_email: false,
+        max_pending_logins: 100,
+    };
+    configure(&mut config);
+    test_app_with(move |app_config| {
+        Arc::get_mut(&mut app_config.app_state).unwrap().oidc = Some(Arc::new(OidcClient::new(config)));
+    })
+    .await
+}
+
+/// /auth/oidc/login 이 돌려준 redirect의 query 값
+async fn start_login(app: &TestApp) -> HashMap<String, String> {
+    let res = app.request(get("/auth/oidc/login")).await;
+    assert_eq!(res.status(), StatusCode::SEE_OTHER);
+    let location = res.headers()[header::LOCATION].to_str().unwrap();
+    reqwest::Url::parse(location).unwrap().query_pairs().into_owned().collect()
+}
+
+/// 사용자가 IdP에서 로그인을 마친 것처럼 code를 등록하고 callback을 부릅니다.
+async fn login(app: &TestApp, idp: &MockIdp, claims: Value) -> axum::http::Response<axum::body::Body> {
+    let params = start_login(app).await;
+    let mut claims = claims;
+    let defaults = json!({
+        "iss": idp.issuer,
+        "aud": CLIENT_ID,
+        "exp": jsonwebtoken::get_current_timestamp() + 300,
+        "nonce": params["nonce"],
+    });
+    for (key, value) in defaults.as_object().unwrap() {
+        claims.as_object_mut().unwrap().entry(key.clone()).or_insert(value.clone());
+    }
+    let code = format!("code-{}", params["state"]);
+    idp.grants
+        .lock()
+        .unwrap()
+        .insert(code.clone(), Grant { claims, code_challenge: params["code_challenge"].clone() });
+
+    app.request(get(&format!("/auth/oidc/callback?code={}&state={}", code, params["state"]))).await
+}
+
+async fn create_user(app: &TestApp, name: &str, email: &str) -> i64 {
+    let res = app.request(with_admin_key(json_request("POST", "/api/v1/users", json!({ "name": name, "email": email })))).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    body_json(res).await["id"].as_i64().unwrap()
+}
+
+#[tokio::test]
+async fn login_creates_a_user_and_replaces_the_previous_login_token() {
+    let idp = start_idp().await;
+    let app = oidc_app(&idp, |_| {}).await;
+
+    let claims = json!({ "sub": "alice-sub", "email": "alice@example.com", "name": "Alice" });
+    let res = login(&app, &idp, claims.clone()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let first = body_json(res).await;
+    assert_eq!(first["user"]["name"], "Alice");
+    assert_eq!(first["token"]["scopes"], json!(["tokens"]));
+    let user_id = first["user"]["id"].as_i64().unwrap();
+
+    // 같은 sub는 같은 사용자. 이전 로그인 토큰은 폐기되어 쓸 수 없습니다.
+    let second = body_json(login(&app, &idp, claims).await).await;
+    assert_eq!(second["user"]["id"], user_id);
+    let tokens_path = format!("/api/v1/users/{}/tokens", user_id);
+    let old_token = first["token"]["token"].as_str().unwrap();
+    let new_token = second["token"]["token"].as_str().unwrap();
+    assert_eq!(app.request(with_bearer(get(&tokens_path), old_token)).await.status(), StatusCode::UNAUTHORIZED);
+    let res = app.request(with_bearer(get(&tokens_path), new_token)).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let tokens = body_json(res).await;
+    let active: Vec<_> = tokens.as_array().unwrap().iter().filter(|t| t["revoked_at"].is_null()).collect();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0]["id"], second["token"]["id"]);
+}
+
+#[tokio::test]
+async fn existing_email_is_not_linked_by_default() {
+    let idp = start_idp().await;
+    let app = oidc_app(&idp, |_| {}).await;
+    let existing = create_user(&app, "bob", "bob@example.com").await;
+
+    let claims = json!({ "sub": "bob-sub", "email": "bob@example.com", "email_verified": true });
+    let user = body_json(login(&app, &idp, claims).await).await["user"].clone();
+    assert_ne!(user["id"], existing);
+}
+
+#[tokio::test]
+async fn existing_email_is_linked_only_when_verified() {
+    let idp = start_idp().await;
+    let app = oidc_app(&idp, |config| config.link_verified_email = true).await;
+    let existing = create_user(&app, "carol", "carol@example.com").await;
+
+    // 확인되지 않은 email (없음, false)로는 기존 계정을 가져갈 수 없습니다.
+    for verified in [json!(null), json!(false)] {
+        let claims = json!({ "sub": format!("attacker-{}", verified), "email": "carol@example.com", "email_verified": verified });
+        let user = body_json(login(&app, &idp, claims).await).await["user"].clone();
+        assert_ne!(user["id"], existing);
+    }
+
+    let claims = json!({ "sub": "carol-sub", "email": "carol@example.com", "email_verified": "true" });
+    let user = body_json(login(&app, &idp, claims).await).await["user"].clone();
+    assert_eq!(user["id"], existing);
+}
+
+#[tokio::test]
+async fn rejects_replayed_state_and_wrong_nonce() {
+    let idp = start_idp().await;
+    let app = oidc_app(&idp, |_| {}).await;
+
+    let res = login(&app, &idp, json!({ "sub": "dave", "email": "dave@example.com", "nonce": "other" })).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert!(body_json(res).await["error"].as_str().unwrap().contains("nonce"));
+
+    // state는 한 번만 쓸 수 있습니다.
+    let params = start_login(&app).await;
+    let callback = format!("/auth/oidc/callback?code=unknown&state={}", params["state"]);
+    assert_eq!(app.request(get(&callback)).await.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(app.request(get(&callback)).await.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn pending_logins_are_capped() {
+    let idp = start_idp().await;
+    let app = oidc_app(&idp, |config| config.max_pending_logins = 2).await;
+
+    start_login(&app).await;
+    start_login(&app).await;
+    let res = app.request(get("/auth/oidc/login")).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
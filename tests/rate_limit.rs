@@ -0,0 +1,147 @@
+// 토큰 버킷 요청 제한: quota 해석, 버킷 채우기, 라우트/키 버킷 함께 소비, 정리, 응답 헤더
+mod common;
+
+use axum::http::StatusCode;
+use axum_rest_api::middleware::{InMemoryStore, Quota, RateLimitConfig, RateLimiter};
+use common::*;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+fn quota(limit: u32, secs: u64) -> Quota {
+    Quota { limit, period: Duration::from_secs(secs) }
+}
+
+fn bucket(name: &str, quota: Quota) -> (String, Quota) {
+    (name.to_string(), quota)
+}
+
+fn limiter(default_quota: Quota, routes: &[(&str, Quota)]) -> RateLimiter {
+    RateLimiter::new(RateLimitConfig {
+        enabled: true,
+        default_quota,
+        route_quotas: routes.iter().map(|(route, q)| (route.to_string(), *q)).collect(),
+        key_quotas: HashMap::new(),
+    })
+}
+
+#[test]
+fn parses_quotas() {
+    assert_eq!(Quota::parse("120/60"), Some(quota(120, 60)));
+    assert_eq!(Quota::parse(" 5 / 1 "), Some(quota(5, 1)));
+    for invalid in ["", "120", "0/60", "10/0", "-1/60", "a/b"] {
+        assert_eq!(Quota::parse(invalid), None, "{}", invalid);
+    }
+}
+
+#[test]
+fn bucket_empties_and_refills_at_the_quota_rate() {
+    let store = InMemoryStore::new();
+    let t0 = Instant::now();
+    let b = [bucket("k", quota(2, 10))];
+
+    let first = store.acquire_at(&b, t0)[0];
+    assert!(first.allowed);
+    assert_eq!((first.limit, first.remaining), (2, 1));
+    assert_eq!(first.reset_after, Duration::from_secs(5));
+    assert!(store.acquire_at(&b, t0)[0].allowed);
+
+    let denied = store.acquire_at(&b, t0)[0];
+    assert!(!denied.allowed);
+    assert_eq!(denied.remaining, 0);
+    assert_eq!(denied.retry_after, Duration::from_secs(5));
+
+    // 5초에 토큰 1개가 채워집니다.
+    assert!(!store.acquire_at(&b, t0 + Duration::from_secs(4))[0].allowed);
+    assert!(store.acquire_at(&b, t0 + Duration::from_secs(5))[0].allowed);
+
+    // 오래 쉬어도 용량(limit)까지만 채워집니다.
+    let later = t0 + Duration::from_secs(3600);
+    assert_eq!(store.acquire_at(&b, later)[0].remaining, 1);
+}
+
+#[test]
+fn denied_buckets_consume_nothing() {
+    let store = InMemoryStore::new();
+    let t0 = Instant::now();
+    // 둘 다 10초에 토큰 1개씩 채워집니다.
+    let route = bucket("route", quota(10, 100));
+    let key = bucket("key", quota(1, 10));
+    let both = [route.clone(), key.clone()];
+
+    let decisions = store.acquire_at(&both, t0);
+    assert!(decisions.iter().all(|d| d.allowed));
+    assert_eq!(decisions[0].remaining, 9);
+
+    // key 버킷이 비어 거부되면 route 버킷도 소비하지 않습니다.
+    let decisions = store.acquire_at(&both, t0);
+    assert!(decisions.iter().all(|d| !d.allowed));
+    assert_eq!(decisions[0].remaining, 9);
+    assert_eq!(decisions[0].retry_after, Duration::ZERO);
+    assert_eq!(decisions[1].retry_after, Duration::from_secs(10));
+
+    let decisions = store.acquire_at(&both, t0 + Duration::from_secs(10));
+    assert!(decisions.iter().all(|d| d.allowed));
+    assert_eq!(decisions[0].remaining, 9);
+}
+
+#[test]
+fn prunes_idle_buckets_by_their_own_period_at_most_once_per_interval() {
+    let store = InMemoryStore::new();
+    let t0 = Instant::now();
+    let long = [bucket("long", quota(1, 3600))];
+    assert!(store.acquire_at(&long, t0)[0].allowed);
+    for i in 0..10_001 {
+        store.acquire_at(&[bucket(&format!("short-{}", i), quota(5, 1))], t0);
+    }
+
+    // 짧은 버킷은 이미 가득 찼지만, 직전 정리 후 정리 간격이 지나지 않았습니다.
+    store.acquire_at(&[bucket("probe-1", quota(5, 1))], t0 + Duration::from_secs(2));
+    assert_eq!(store.len(), 10_003);
+
+    let later = t0 + Duration::from_secs(30);
+    store.acquire_at(&[bucket("probe-2", quota(5, 1))], later);
+    assert_eq!(store.len(), 2);
+
+    // 한 시간짜리 버킷은 남아 있으므로 아직 채워지지 않았습니다.
+    assert!(!store.acquire_at(&long, later)[0].allowed);
+}
+
+#[tokio::test]
+async fn limiter_reports_the_tighter_bucket() {
+    let limiter = limiter(quota(5, 60), &[("/strict", quota(2, 60))]);
+
+    let decision = limiter.check("/strict", "ip:1").await;
+    assert!(decision.allowed);
+    assert_eq!((decision.limit, decision.remaining), (2, 1));
+    assert!(limiter.check("/strict", "ip:1").await.allowed);
+
+    // 라우트 quota로 거부된 요청은 키 quota를 소비하지 않습니다.
+    let denied = limiter.check("/strict", "ip:1").await;
+    assert!(!denied.allowed);
+    assert_eq!(denied.limit, 2);
+
+    let other = limiter.check("/other", "ip:1").await;
+    assert!(other.allowed);
+    assert_eq!((other.limit, other.remaining), (5, 2));
+
+    // 다른 키는 따로 셉니다.
+    assert!(limiter.check("/strict", "ip:2").await.allowed);
+}
+
+#[tokio::test]
+async fn middleware_returns_429_with_rate_limit_headers() {
+    let app = test_app_with(|config| {
+        let state = std::sync::Arc::get_mut(&mut config.app_state).unwrap();
+        state.rate_limiter = limiter(quota(1, 60), &[]);
+    })
+    .await;
+
+    let res = app.request(get("/health")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["ratelimit-limit"], "1");
+    assert_eq!(res.headers()["ratelimit-remaining"], "0");
+
+    let res = app.request(get("/health")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(res.headers()["retry-after"], "60");
+}
@@ -0,0 +1,69 @@
+// 느린 쿼리 / 느린 요청 기록과 SQL 값 가리기
+use axum_rest_api::metrics::slow::{redact_sql, SlowEventLog};
+use axum_rest_api::models::SlowEventKind;
+use std::time::Duration;
+
+#[test]
+fn redacts_quoted_strings() {
+    assert_eq!(
+        redact_sql("SELECT id FROM axum_users WHERE email = 'alice@example.com' AND name = \"Alice\""),
+        "SELECT id FROM axum_users WHERE email = ? AND name = ?"
+    );
+    assert_eq!(redact_sql("SELECT ''"), "SELECT ?");
+}
+
+#[test]
+fn redacts_escaped_quotes_without_leaking_the_rest() {
+    // SQL 표준 ('') 과 백슬래시 이스케이프 모두 문자열 안으로 봅니다.
+    assert_eq!(redact_sql("WHERE name = 'O''Brien' AND x = ?"), "WHERE name = ? AND x = ?");
+    assert_eq!(redact_sql(r"WHERE name = 'it\'s secret' AND x = ?"), "WHERE name = ? AND x = ?");
+    assert_eq!(redact_sql(r"WHERE path = 'C:\\' AND x = ?"), "WHERE path = ? AND x = ?");
+    // 닫히지 않은 따옴표는 끝까지 가립니다.
+    assert_eq!(redact_sql("WHERE token = 'abc"), "WHERE token = ?");
+}
+
+#[test]
+fn redacts_numbers_but_not_identifiers() {
+    assert_eq!(
+        redact_sql("SELECT col1, t2.x FROM t2 WHERE id = 42 AND score > 3.5 AND flags = 0x1F LIMIT 10 OFFSET -20"),
+        "SELECT col1, t2.x FROM t2 WHERE id = ? AND score > ? AND flags = ? LIMIT ? OFFSET -?"
+    );
+    // bind 파라미터는 그대로 둡니다.
+    assert_eq!(redact_sql("UPDATE t SET a = ? WHERE id = ?"), "UPDATE t SET a = ? WHERE id = ?");
+}
+
+#[test]
+fn redacts_in_lists() {
+    assert_eq!(
+        redact_sql("DELETE FROM t WHERE id IN (1, 2, 3) OR name IN ('a','b')"),
+        "DELETE FROM t WHERE id IN (?, ?, ?) OR name IN (?,?)"
+    );
+}
+
+#[test]
+fn collapses_whitespace() {
+    assert_eq!(
+        redact_sql("  SELECT id\n\tFROM axum_users\r\n   WHERE id = 7  \n"),
+        "SELECT id FROM axum_users WHERE id = ?"
+    );
+}
+
+#[test]
+fn records_events_over_the_threshold_with_their_kind() {
+    let log = SlowEventLog::from_env();
+    log.record_query("fast_query", "SELECT ?", Duration::ZERO);
+    log.record_query("list_users", "SELECT id FROM axum_users LIMIT ?", log.query_threshold);
+    log.record_request("GET", "/api/v1/users", log.request_threshold + Duration::from_millis(5));
+
+    let events = log.recent(10);
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].kind, SlowEventKind::Request);
+    assert_eq!(events[0].name, "GET /api/v1/users");
+    assert_eq!(events[0].sql, None);
+    assert_eq!(events[1].kind, SlowEventKind::Query);
+    assert_eq!(events[1].sql.as_deref(), Some("SELECT id FROM axum_users LIMIT ?"));
+
+    let json = serde_json::to_value(&events[0]).unwrap();
+    assert_eq!(json["kind"], "request");
+    assert_eq!(log.recent(1).len(), 1);
+}
@@ -0,0 +1,92 @@
+// OTLP/HTTP trace 내보내기: 로컬 수집기 대신 protobuf를 받는 서버를 띄워 span을 확인합니다.
+// 전역 subscriber를 설치하므로 이 파일에는 테스트를 하나만 둡니다.
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use axum_rest_api::telemetry;
+use common::*;
+use opentelemetry_proto::tonic::collector::trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse};
+use opentelemetry_proto::tonic::trace::v1::{span::SpanKind, Span};
+use prost::Message;
+use std::sync::{Arc, Mutex};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+type Received = Arc<Mutex<Vec<Span>>>;
+
+async fn export(State(received): State<Received>, body: Bytes) -> (StatusCode, [(header::HeaderName, &'static str); 1], Vec<u8>) {
+    let request = ExportTraceServiceRequest::decode(body).expect("not an OTLP trace export");
+    let spans = request
+        .resource_spans
+        .into_iter()
+        .flat_map(|r| r.scope_spans)
+        .flat_map(|s| s.spans);
+    received.lock().unwrap().extend(spans);
+    let response = ExportTraceServiceResponse::default().encode_to_vec();
+    (StatusCode::OK, [(header::CONTENT_TYPE, "application/x-protobuf")], response)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn exports_request_and_query_spans_joined_to_the_incoming_trace() {
+    let receiver = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    receiver.set_nonblocking(true).unwrap();
+    // SAFETY: 다른 스레드가 환경 변수를 읽기 전, 이 테스트 하나만 도는 프로세스에서 설정합니다.
+    unsafe {
+        std::env::set_var(
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+            format!("http://{}/v1/traces", receiver.local_addr().unwrap()),
+        );
+    }
+    // OTLP exporter의 blocking HTTP client는 tokio runtime 밖에서 만들어야 합니다.
+    telemetry::init_tracing();
+
+    let received = Received::default();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let collector = Router::new().route("/v1/traces", post(export)).with_state(received.clone());
+        let listener = tokio::net::TcpListener::from_std(receiver).unwrap();
+        tokio::spawn(async move { axum::serve(listener, collector).await.unwrap() });
+
+        let app = test_app().await;
+        let mut req = get("/api/v1/users");
+        req.headers_mut()
+            .insert("traceparent", format!("00-{}-{}-01", TRACE_ID, PARENT_SPAN_ID).parse().unwrap());
+        assert_eq!(app.request(req).await.status(), StatusCode::OK);
+    });
+    // 남은 span을 내보냅니다. 수집기는 runtime의 worker 스레드에서 계속 응답합니다.
+    telemetry::shutdown_tracing();
+
+    let spans = received.lock().unwrap();
+    let server = spans
+        .iter()
+        .find(|s| s.name == "GET /api/v1/users")
+        .unwrap_or_else(|| panic!("no request span in {:?}", spans.iter().map(|s| &s.name).collect::<Vec<_>>()));
+    assert_eq!(server.kind, SpanKind::Server as i32);
+    assert_eq!(hex(&server.trace_id), TRACE_ID);
+    assert_eq!(hex(&server.parent_span_id), PARENT_SPAN_ID);
+
+    let query = spans
+        .iter()
+        .find(|s| s.kind == SpanKind::Client as i32 && s.parent_span_id == server.span_id)
+        .expect("no query span under the request span");
+    assert_eq!(query.trace_id, server.trace_id);
+    let attribute = |key: &str| {
+        query
+            .attributes
+            .iter()
+            .find(|a| a.key == key)
+            .and_then(|a| a.value.as_ref())
+            .map(|v| format!("{:?}", v.value))
+    };
+    assert!(attribute("db.system").unwrap().contains("sqlite"));
+    assert!(attribute("db.query.text").unwrap().contains("SELECT"));
+}
@@ -0,0 +1,236 @@
+// HTTPS: 테스트 중에 만든 인증서로 서비스하고, 파일이 바뀌면 재시작 없이 새 인증서를 씁니다.
+// mTLS: 관리자 라우트에 클라이언트 인증서를 요구하는 정책을 확인합니다.
+mod common;
+
+use axum::http::{header, StatusCode};
+use axum_rest_api::tls::{self, ClientCertPolicy, ReloadableCert, TlsConfig};
+use common::*;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// 테스트용 CA. `issue`로 이 CA가 서명한 인증서를 만듭니다.
+struct TestCa {
+    cert: Certificate,
+    key: KeyPair,
+}
+
+struct Issued {
+    cert_pem: String,
+    key_pem: String,
+    der: Vec<u8>,
+}
+
+impl TestCa {
+    fn new(name: &str) -> Self {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.distinguished_name.push(DnType::CommonName, name);
+        let cert = params.self_signed(&key).unwrap();
+        TestCa { cert, key }
+    }
+
+    fn issue(&self, common_name: &str, usage: ExtendedKeyUsagePurpose) -> Issued {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params.distinguished_name.push(DnType::CommonName, common_name);
+        params.extended_key_usages = vec![usage];
+        let cert = params.signed_by(&key, &self.cert, &self.key).unwrap();
+        Issued { cert_pem: cert.pem(), key_pem: key.serialize_pem(), der: cert.der().to_vec() }
+    }
+
+    fn server_cert(&self, common_name: &str) -> Issued {
+        self.issue(common_name, ExtendedKeyUsagePurpose::ServerAuth)
+    }
+
+    fn client_identity(&self, common_name: &str) -> reqwest::Identity {
+        let issued = self.issue(common_name, ExtendedKeyUsagePurpose::ClientAuth);
+        reqwest::Identity::from_pem(format!("{}{}", issued.cert_pem, issued.key_pem).as_bytes()).unwrap()
+    }
+
+    fn root(&self) -> reqwest::Certificate {
+        reqwest::Certificate::from_pem(self.cert.pem().as_bytes()).unwrap()
+    }
+}
+
+/// 인증서 파일을 두는 임시 디렉터리. drop되면 지웁니다.
+struct CertDir(PathBuf);
+
+impl CertDir {
+    fn new() -> Self {
+        let dir = std::env::temp_dir().join(format!("axum-rest-api-tls-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        CertDir(dir)
+    }
+
+    fn write(&self, name: &str, contents: &str) -> PathBuf {
+        let path = self.0.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn install(&self, issued: &Issued) {
+        self.write("cert.pem", &issued.cert_pem);
+        self.write("key.pem", &issued.key_pem);
+    }
+
+    fn config(&self) -> TlsConfig {
+        TlsConfig {
+            cert_path: self.0.join("cert.pem"),
+            key_path: self.0.join("key.pem"),
+            reload_interval: Duration::from_millis(50),
+            redirect_port: None,
+            client_ca_path: None,
+        }
+    }
+}
+
+impl Drop for CertDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 새 연결로 요청하고 서버가 보여 준 인증서(DER)를 돌려줍니다.
+async fn served_certificate(server: &SpawnedApp, ca: &TestCa) -> Vec<u8> {
+    let client = reqwest::Client::builder()
+        .add_root_certificate(ca.root())
+        .tls_info(true)
+        .build()
+        .unwrap();
+    let res = client.get(server.url("/health")).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let info = res.extensions().get::<reqwest::tls::TlsInfo>().expect("no TLS info");
+    info.peer_certificate().expect("no server certificate").to_vec()
+}
+
+#[tokio::test]
+async fn reloads_the_certificate_when_the_files_change() {
+    let ca = TestCa::new("Test CA");
+    let dir = CertDir::new();
+    let first = ca.server_cert("first");
+    dir.install(&first);
+
+    let cert = Arc::new(ReloadableCert::load(dir.config()).unwrap());
+    let stop = CancellationToken::new();
+    tls::spawn_reloader(cert.clone(), stop.clone());
+    let app = test_app().await;
+    let server = app.spawn_tls(tls::acceptor(cert.clone()).unwrap()).await;
+    assert_eq!(served_certificate(&server, &ca).await, first.der);
+
+    let second = ca.server_cert("second");
+    dir.install(&second);
+    let mut served = Vec::new();
+    for _ in 0..100 {
+        served = served_certificate(&server, &ca).await;
+        if served == second.der {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(served, second.der, "certificate was not reloaded");
+
+    // 새 파일을 읽지 못하면 기존 인증서를 계속 씁니다.
+    dir.write("key.pem", "not a key");
+    assert!(cert.reload().is_err());
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(served_certificate(&server, &ca).await, second.der);
+
+    stop.cancel();
+}
+
+#[tokio::test]
+async fn rejects_a_key_that_does_not_match_the_certificate() {
+    let ca = TestCa::new("Test CA");
+    let dir = CertDir::new();
+    let cert = ca.server_cert("server");
+    dir.write("cert.pem", &cert.cert_pem);
+    dir.write("key.pem", &ca.server_cert("other").key_pem);
+    assert!(ReloadableCert::load(dir.config()).is_err());
+
+    dir.write("cert.pem", "");
+    assert!(ReloadableCert::load(dir.config()).is_err());
+}
+
+#[tokio::test]
+async fn redirects_http_to_the_https_port() {
+    use tower::ServiceExt;
+
+    let redirect = |https_port: u16, uri: &str, host: &str| {
+        let req = axum::http::Request::get(uri)
+            .header(header::HOST, host)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        tls::redirect_router(https_port).oneshot(req)
+    };
+
+    let res = redirect(8443, "/api/v1/users?page=2", "example.com:3080").await.unwrap();
+    assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(res.headers()[header::LOCATION], "https://example.com:8443/api/v1/users?page=2");
+
+    let res = redirect(443, "/", "example.com").await.unwrap();
+    assert_eq!(res.headers()[header::LOCATION], "https://example.com/");
+}
+
+#[tokio::test]
+async fn admin_routes_require_an_allowed_client_certificate() {
+    let ca = TestCa::new("Test CA");
+    let client_ca = TestCa::new("Client CA");
+    let dir = CertDir::new();
+    dir.install(&ca.server_cert("server"));
+    let config = TlsConfig {
+        client_ca_path: Some(dir.write("client-ca.pem", &client_ca.cert.pem())),
+        ..dir.config()
+    };
+    let acceptor = tls::acceptor(Arc::new(ReloadableCert::load(config).unwrap())).unwrap();
+
+    let app = test_app_with(|config| {
+        Arc::get_mut(&mut config.app_state).unwrap().admin_client_cert = ClientCertPolicy {
+            required_for_admin: true,
+            allowed_subjects: vec!["cn=ops-admin".to_string()],
+        };
+    })
+    .await;
+    let server = app.spawn_tls(acceptor).await;
+
+    let client = |identity: Option<reqwest::Identity>| {
+        let builder = reqwest::Client::builder().add_root_certificate(ca.root());
+        match identity {
+            Some(identity) => builder.identity(identity),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    };
+    let get_app_state = |client: reqwest::Client, admin_key: bool| {
+        let mut req = client.get(server.url("/admin/get_app_state"));
+        if admin_key {
+            req = req.header("X-Admin-API-Key", ADMIN_KEY);
+        }
+        async move { req.send().await.map(|res| res.status()) }
+    };
+
+    let admin = client(Some(client_ca.client_identity("ops-admin")));
+    assert_eq!(get_app_state(admin.clone(), true).await.unwrap(), StatusCode::OK);
+    // 인증서가 있어도 관리자 키는 필요합니다.
+    assert_eq!(get_app_state(admin, false).await.unwrap(), StatusCode::UNAUTHORIZED);
+
+    // 인증서 없는 연결도 받지만 관리자 라우트는 거부합니다.
+    let anonymous = client(None);
+    assert_eq!(get_app_state(anonymous.clone(), true).await.unwrap(), StatusCode::UNAUTHORIZED);
+    let res = anonymous.get(server.url("/health")).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // 허용 목록에 없는 subject
+    let other = client(Some(client_ca.client_identity("someone-else")));
+    assert_eq!(get_app_state(other, true).await.unwrap(), StatusCode::UNAUTHORIZED);
+
+    // 설정한 CA가 서명하지 않은 인증서는 핸드셰이크에서 거부합니다.
+    let untrusted = client(Some(TestCa::new("Other CA").client_identity("ops-admin")));
+    assert!(get_app_state(untrusted, true).await.is_err());
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum_rest_api::auth::{self, Principal};
+use common::*;
+use serde_json::json;
+
+async fn create_user(app: &TestApp) {
+    let res = app
+        .request(json_request("POST", "/create-user-db", json!({ "name": "alice", "email": "alice@example.com" })))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn token_routes_require_credentials() {
+    let app = test_app().await;
+    let res = app.request(get("/users/1/tokens")).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = app.request(with_bearer(get("/users/1/tokens"), "arat_not-a-real-token")).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn token_lifecycle() {
+    let app = test_app().await;
+    create_user(&app).await;
+
+    // 관리자가 토큰을 발급
+    let res = app
+        .request(with_admin_key(json_request(
+            "POST",
+            "/users/1/tokens",
+            json!({ "name": "ci", "scopes": ["tokens", "users:read"] }),
+        )))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let created = body_json(res).await;
+    let token = created["token"].as_str().unwrap().to_string();
+    let token_id = created["id"].as_i64().unwrap();
+    assert!(token.starts_with("arat_"));
+
+    // 발급받은 토큰으로 본인 토큰 목록 조회
+    let res = app.request(with_bearer(get("/users/1/tokens"), &token)).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let tokens = body_json(res).await;
+    assert_eq!(tokens.as_array().unwrap().len(), 1);
+    assert_eq!(tokens[0]["name"], "ci");
+    assert!(tokens[0]["last_used_at"].is_i64());
+    assert!(tokens[0].get("token").is_none());
+
+    // 가진 것보다 넓은 권한은 만들 수 없음
+    let res = app
+        .request(with_bearer(
+            json_request("POST", "/users/1/tokens", json!({ "name": "wide", "scopes": ["admin"] })),
+            &token,
+        ))
+        .await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    // 다른 사용자의 토큰은 관리할 수 없음
+    let res = app.request(with_bearer(get("/users/2/tokens"), &token)).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    // 폐기 후에는 인증 실패
+    let revoke = Request::delete(format!("/users/1/tokens/{}", token_id)).body(Body::empty()).unwrap();
+    let res = app.request(with_bearer(revoke, &token)).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = app.request(with_bearer(get("/users/1/tokens"), &token)).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let revoke = Request::delete(format!("/users/1/tokens/{}", token_id)).body(Body::empty()).unwrap();
+    let res = app.request(with_admin_key(revoke)).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn create_token_validates_input() {
+    let app = test_app().await;
+    create_user(&app).await;
+
+    let res = app
+        .request(with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "", "scopes": ["tokens"] }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app
+        .request(with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "x", "scopes": ["nope"] }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn token_format_and_hashing() {
+    let token = auth::generate_token();
+    assert!(token.starts_with(auth::TOKEN_PREFIX));
+    assert_eq!(token.len(), auth::TOKEN_PREFIX.len() + 64);
+    assert_ne!(token, auth::generate_token());
+
+    // 저장하는 값은 원문의 SHA-256 (hex)
+    assert_eq!(auth::hash_token("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_ne!(auth::hash_token(&token), auth::hash_token(&auth::generate_token()));
+
+    assert_eq!(auth::display_prefix(&token), token[..auth::TOKEN_PREFIX.len() + 4]);
+    assert_eq!(auth::parse_scopes(" users:read, ,tokens,"), ["users:read", "tokens"]);
+}
+
+#[test]
+fn scope_checks() {
+    let admin = Principal::Admin;
+    assert!(admin.has_scope(auth::SCOPE_ADMIN));
+    assert!(admin.can_manage_tokens_of(42));
+
+    let user = |scopes: &[&str]| Principal::User {
+        user_id: 7,
+        token_id: 1,
+        scopes: scopes.iter().map(|s| s.to_string()).collect(),
+    };
+    let reader = user(&[auth::SCOPE_USERS_READ]);
+    assert!(reader.has_scope(auth::SCOPE_USERS_READ));
+    assert!(!reader.has_scope(auth::SCOPE_ADMIN));
+    // 본인 토큰이라도 `tokens` 스코프가 있어야 하고, 다른 사용자의 토큰은 관리할 수 없습니다.
+    assert!(!reader.can_manage_tokens_of(7));
+    let manager = user(&[auth::SCOPE_TOKENS]);
+    assert!(manager.can_manage_tokens_of(7));
+    assert!(!manager.can_manage_tokens_of(8));
+}
+
+#[tokio::test]
+async fn listing_reports_corrupt_rows_instead_of_defaults() {
+    let app = test_app().await;
+    create_user(&app).await;
+    let res = app
+        .request(with_admin_key(json_request("POST", "/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let res = app.request(with_admin_key(get("/users/1/tokens"))).await;
+    let tokens = body_json(res).await;
+    assert!(tokens[0]["last_used_at"].is_null());
+
+    // 숫자가 아닌 created_at을 0으로 바꿔 보여 주지 않고 500
+    sqlx::query("UPDATE axum_api_tokens SET created_at = 'yesterday'").execute(&app.config.db_pool).await.unwrap();
+    let res = app.request(with_admin_key(get("/users/1/tokens"))).await;
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::*;
+use serde_json::json;
+
+#[tokio::test]
+async fn create_and_list_db_users() {
+    let app = test_app().await;
+
+    let res = app
+        .request(json_request("POST", "/create-user-db", json!({ "name": "alice", "email": "alice@example.com" })))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(body_json(res).await["user"]["name"], "alice");
+
+    let res = app.request(get("/axum-users")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let users = body_json(res).await;
+    assert_eq!(users.as_array().unwrap().len(), 1);
+    assert_eq!(users[0]["id"], 1);
+    assert_eq!(users[0]["email"], "alice@example.com");
+}
+
+#[tokio::test]
+async fn concurrent_creates_return_their_own_ids() {
+    let app = test_app().await;
+    let create = || app.request(with_admin_key(json_request("POST", "/api/v1/users", json!({ "name": "same", "email": "same@example.com" }))));
+    let responses = futures_util::future::join_all((0..8).map(|_| create())).await;
+
+    let mut ids = Vec::new();
+    for res in responses {
+        assert_eq!(res.status(), StatusCode::CREATED);
+        ids.push(body_json(res).await["id"].as_i64().unwrap());
+    }
+    ids.sort();
+    assert_eq!(ids, (1..=8).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn create_user_db_rejects_invalid_json() {
+    let app = test_app().await;
+    let res = app.request(json_request("POST", "/create-user-db", json!({ "name": "bob" }))).await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn static_user_routes() {
+    let app = test_app().await;
+
+    let res = app.request(json_request("POST", "/create-user", json!({}))).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let res = app.request(get("/users")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let req = axum::http::Request::delete("/delete-user/2").body(axum::body::Body::empty()).unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_json(res).await["id"], 2);
+
+    let req = axum::http::Request::delete("/delete-user/1").body(axum::body::Body::empty()).unwrap();
+    let res = app.request(req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn item_routes() {
+    let app = test_app().await;
+
+    let res = app.request(get("/item/42?number=2")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_string(res).await, "Item ID: 42, Page Number: 2");
+
+    // number가 없으면 1쪽
+    let res = app.request(get("/item/42")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_string(res).await, "Item ID: 42, Page Number: 1");
+
+    let res = app.request(get("/item/1?number=2")).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = app.request(json_request("POST", "/add-item", json!({ "title": "Some random item" }))).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_string(res).await, "Item added: Some random item");
+}
+
+#[tokio::test]
+async fn v1_user_changes_require_the_write_scope() {
+    let app = test_app().await;
+    let create = |name: &str| json_request("POST", "/api/v1/users", json!({ "name": name, "email": format!("{}@example.com", name) }));
+    let token = |scope: &str| {
+        let req = with_admin_key(json_request("POST", "/api/v1/users/1/tokens", json!({ "name": scope, "scopes": [scope] })));
+        async { body_json(app.request(req).await).await["token"].as_str().unwrap().to_string() }
+    };
+    assert_eq!(app.request(with_admin_key(create("alice"))).await.status(), StatusCode::CREATED);
+    let reader = token("users:read").await;
+    let writer = token("users:write").await;
+
+    // 인증이 없으면 401, users:write 스코프가 없으면 403
+    assert_eq!(app.request(create("eve")).await.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(app.request(with_bearer(create("eve"), &reader)).await.status(), StatusCode::FORBIDDEN);
+    let rename = || json_request("PATCH", "/api/v1/users/1", json!({ "name": "mallory" }));
+    assert_eq!(app.request(rename()).await.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(app.request(with_bearer(rename(), &reader)).await.status(), StatusCode::FORBIDDEN);
+    let remove = || axum::http::Request::delete("/api/v1/users/1").body(axum::body::Body::empty()).unwrap();
+    assert_eq!(app.request(remove()).await.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(app.request(with_bearer(remove(), &reader)).await.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_json(app.request(get("/api/v1/users/1")).await).await["name"], "alice");
+
+    // 읽기는 그대로 열려 있습니다.
+    assert_eq!(app.request(get("/api/v1/users")).await.status(), StatusCode::OK);
+
+    assert_eq!(app.request(with_bearer(create("bob"), &writer)).await.status(), StatusCode::CREATED);
+    assert_eq!(app.request(with_bearer(rename(), &writer)).await.status(), StatusCode::OK);
+    assert_eq!(app.request(with_bearer(remove(), &writer)).await.status(), StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,103 @@
+// /api/v1 리소스 경로와 예전 경로의 Deprecation/Sunset 헤더
+mod common;
+
+use axum::http::{header, StatusCode};
+use axum_rest_api::middleware::{successor_path, DeprecationConfig};
+use common::*;
+use serde_json::json;
+use std::sync::Arc;
+
+fn delete(uri: &str) -> axum::http::Request<axum::body::Body> {
+    axum::http::Request::delete(uri).body(axum::body::Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn v1_user_lifecycle() {
+    let app = test_app().await;
+
+    let res = app
+        .request(with_admin_key(json_request("POST", "/api/v1/users", json!({ "name": "alice", "email": "alice@example.com" }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers()[header::LOCATION], "/api/v1/users/1");
+    assert_eq!(body_json(res).await, json!({ "id": 1, "name": "alice", "email": "alice@example.com" }));
+
+    let res = app.request(get("/api/v1/users/1")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_json(res).await["email"], "alice@example.com");
+
+    // 예전 경로로 만든 사용자도 같은 테이블에 있습니다.
+    app.request(json_request("POST", "/create-user-db", json!({ "name": "bob", "email": "bob@example.com" }))).await;
+    let users = body_json(app.request(get("/api/v1/users")).await).await;
+    assert_eq!(users.as_array().unwrap().len(), 2);
+
+    let res = app
+        .request(with_admin_key(json_request("POST", "/api/v1/users/1/tokens", json!({ "name": "ci", "scopes": ["tokens"] }))))
+        .await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let token = body_json(res).await["token"].as_str().unwrap().to_string();
+
+    // 사용자를 지우면 토큰도 함께 지워집니다 (ON DELETE CASCADE).
+    assert_eq!(app.request(with_admin_key(delete("/api/v1/users/1"))).await.status(), StatusCode::NO_CONTENT);
+    assert_eq!(app.request(get("/api/v1/users/1")).await.status(), StatusCode::NOT_FOUND);
+    assert_eq!(app.request(with_admin_key(delete("/api/v1/users/1"))).await.status(), StatusCode::NOT_FOUND);
+    let res = app.request(with_bearer(get("/api/v1/users/1/tokens"), &token)).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn v1_items() {
+    let app = test_app().await;
+
+    let res = app.request(get("/api/v1/items/42?number=2")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_string(res).await, "Item ID: 42, Page Number: 2");
+    assert_eq!(app.request(get("/api/v1/items/1?number=2")).await.status(), StatusCode::NOT_FOUND);
+
+    let res = app.request(json_request("POST", "/api/v1/items", json!({ "title": "lamp" }))).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(body_json(res).await, json!({ "title": "lamp" }));
+}
+
+#[tokio::test]
+async fn legacy_routes_announce_their_successor() {
+    let app = test_app_with(|config| {
+        config.deprecation = Arc::new(DeprecationConfig {
+            deprecated_at: httpdate::parse_http_date("Mon, 19 Oct 2026 00:00:00 GMT").unwrap(),
+            sunset: httpdate::parse_http_date("Mon, 19 Apr 2027 00:00:00 GMT").unwrap(),
+        });
+    })
+    .await;
+
+    let res = app.request(get("/axum-users")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["deprecation"], "@1792368000");
+    assert_eq!(res.headers()["sunset"], "Mon, 19 Apr 2027 00:00:00 GMT");
+    assert_eq!(res.headers()["link"], "</api/v1/users>; rel=\"successor-version\"");
+
+    // 오류 응답에도 붙습니다.
+    let res = app.request(delete("/delete-user/1")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.headers()["link"], "</api/v1/users/1>; rel=\"successor-version\"");
+
+    // 인증 실패(401)도 예전 경로이므로 헤더가 붙습니다.
+    let res = app.request(delete("/users/3/tokens/7")).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert!(res.headers().contains_key("deprecation"));
+
+    for uri in ["/api/v1/users", "/health", "/admin/get_app_state"] {
+        let res = app.request(get(uri)).await;
+        assert!(!res.headers().contains_key("deprecation"), "{} must not be deprecated", uri);
+        assert!(!res.headers().contains_key("sunset"), "{} must not have a sunset", uri);
+    }
+}
+
+#[test]
+fn successor_paths_carry_path_parameters() {
+    assert_eq!(successor_path("/item/:id", "/item/42").as_deref(), Some("/api/v1/items/42"));
+    assert_eq!(
+        successor_path("/users/:id/tokens/:token_id", "/users/3/tokens/7").as_deref(),
+        Some("/api/v1/users/3/tokens/7")
+    );
+    assert_eq!(successor_path("/health", "/health"), None);
+}
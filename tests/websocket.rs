@@ -0,0 +1,234 @@
+// GET /ws (WebSocket): 인증, 토픽 구독, 느린 클라이언트, heartbeat, 연결 수
+mod common;
+
+use axum_rest_api::ws::{WsConfig, WsHub};
+use axum_rest_api_client::models::{
+    BodyItem, CreateTokenRequest, CreateUserRequest, ItemChange, UserChange, WsMessage, WsTopic,
+};
+use axum_rest_api_client::{Auth, Client, Error, WsSubscription};
+use common::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn ws_config() -> WsConfig {
+    WsConfig {
+        max_connections: 10,
+        send_queue: 64,
+        send_timeout: Duration::from_secs(10),
+        ping_interval: Duration::from_secs(30),
+        pong_timeout: Duration::from_secs(10),
+    }
+}
+
+fn with_ws(config: WsConfig) -> impl FnOnce(&mut axum_rest_api::AppConfig) {
+    move |app_config| {
+        Arc::get_mut(&mut app_config.app_state).unwrap().websockets = Arc::new(WsHub::new(config));
+    }
+}
+
+async fn next(subscription: &mut WsSubscription) -> WsMessage {
+    tokio::time::timeout(Duration::from_secs(5), subscription.next())
+        .await
+        .expect("no message within 5 seconds")
+        .unwrap()
+        .expect("connection closed")
+}
+
+async fn websocket_stats(admin: &Client) -> serde_json::Value {
+    admin.get_app_state().await.unwrap()["websocket"].clone()
+}
+
+#[tokio::test]
+async fn requires_authentication() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+
+    let anonymous = Client::builder(&server.base_url).build().unwrap();
+    match anonymous.websocket(&[WsTopic::Items]).await {
+        Err(Error::Api { status, .. }) => assert_eq!(status, 401),
+        Err(other) => panic!("expected 401, got {:?}", other),
+        Ok(_) => panic!("expected 401"),
+    }
+}
+
+#[tokio::test]
+async fn streams_user_and_item_changes_to_subscribers() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+
+    let mut ws = admin.websocket(&[WsTopic::Users, WsTopic::Items]).await.unwrap();
+    match next(&mut ws).await {
+        WsMessage::Subscribed { topics } => assert_eq!(topics, vec![WsTopic::Users, WsTopic::Items]),
+        other => panic!("unexpected {:?}", other),
+    }
+    let stats = websocket_stats(&admin).await;
+    assert_eq!(stats["connections"], 1);
+    assert_eq!(stats["subscribers"]["users"], 1);
+
+    let alice = admin
+        .v1()
+        .create_user(&CreateUserRequest { name: "alice".to_string(), email: "alice@example.com".to_string() })
+        .await
+        .unwrap();
+    admin.v1().create_item(&BodyItem { title: "lamp".to_string() }).await.unwrap();
+    // 예전 경로로 추가한 아이템도 알립니다.
+    admin.add_item(&BodyItem { title: "desk".to_string() }).await.unwrap();
+
+    match next(&mut ws).await {
+        WsMessage::User { event } => assert_eq!((event.change, event.user.id), (UserChange::Created, alice.id)),
+        other => panic!("unexpected {:?}", other),
+    }
+    let mut titles = Vec::new();
+    for _ in 0..2 {
+        match next(&mut ws).await {
+            WsMessage::Item { event } => {
+                assert_eq!(event.change, ItemChange::Created);
+                titles.push(event.item.title);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+    assert_eq!(titles, ["lamp", "desk"]);
+
+    // 구독을 끊은 토픽은 더 받지 않습니다.
+    ws.unsubscribe(&[WsTopic::Items]).await.unwrap();
+    assert!(matches!(next(&mut ws).await, WsMessage::Subscribed { topics } if topics == [WsTopic::Users]));
+    admin.v1().create_item(&BodyItem { title: "chair".to_string() }).await.unwrap();
+    admin.v1().delete_user(alice.id).await.unwrap();
+    assert!(matches!(next(&mut ws).await, WsMessage::User { event } if event.change == UserChange::Deleted));
+
+    ws.close().await.unwrap();
+    for _ in 0..50 {
+        if websocket_stats(&admin).await["connections"] == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let stats = websocket_stats(&admin).await;
+    assert_eq!(stats["connections"], 0);
+    assert_eq!(stats["subscribers"]["users"], 0);
+}
+
+#[tokio::test]
+async fn users_topic_requires_the_read_scope() {
+    let app = test_app().await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+    let bob = admin
+        .v1()
+        .create_user(&CreateUserRequest { name: "bob".to_string(), email: "bob@example.com".to_string() })
+        .await
+        .unwrap();
+    let token = admin
+        .v1()
+        .create_token(bob.id, &CreateTokenRequest { name: "ws".to_string(), scopes: vec!["tokens".to_string()] })
+        .await
+        .unwrap();
+
+    let bob_client = admin.with_auth(Auth::Bearer(token.token));
+    let mut ws = bob_client.websocket(&[WsTopic::Users, WsTopic::Items]).await.unwrap();
+    match next(&mut ws).await {
+        WsMessage::Error { error } => assert!(error.contains("users:read"), "{}", error),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(matches!(next(&mut ws).await, WsMessage::Subscribed { topics } if topics == [WsTopic::Items]));
+
+    // 구독 없이 연결만 할 수도 있습니다.
+    let mut idle = bob_client.websocket(&[]).await.unwrap();
+    idle.unsubscribe(&[WsTopic::Users]).await.unwrap();
+    assert!(matches!(next(&mut idle).await, WsMessage::Subscribed { topics } if topics.is_empty()));
+}
+
+#[tokio::test]
+async fn slow_clients_get_a_lagged_notice_instead_of_unbounded_buffering() {
+    let config = WsConfig { send_queue: 2, ..ws_config() };
+    let app = test_app_with(with_ws(config)).await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+
+    let mut ws = admin.websocket(&[WsTopic::Items]).await.unwrap();
+    assert!(matches!(next(&mut ws).await, WsMessage::Subscribed { .. }));
+
+    // 한꺼번에 발행하면 연결의 대기열(2)이 넘칩니다.
+    let item_events = &app.config.app_state.item_events;
+    for i in 0..200 {
+        item_events.publish(ItemChange::Created, BodyItem { title: format!("item {}", i) });
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let last = item_events.publish(ItemChange::Created, BodyItem { title: "last".to_string() });
+
+    let (mut received, mut missed) = (0u64, 0u64);
+    loop {
+        match next(&mut ws).await {
+            WsMessage::Item { event } => {
+                received += 1;
+                if event.id == last {
+                    break;
+                }
+            }
+            WsMessage::Lagged { topic, missed: n } => {
+                assert_eq!(topic, WsTopic::Items);
+                missed += n;
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+    assert!(missed > 0, "expected dropped events");
+    assert_eq!(received + missed, 201);
+    assert_eq!(websocket_stats(&admin).await["dropped_events"], missed);
+}
+
+#[tokio::test]
+async fn heartbeat_closes_silent_connections() {
+    let config = WsConfig { ping_interval: Duration::from_secs(1), pong_timeout: Duration::from_secs(1), ..ws_config() };
+    let app = test_app_with(with_ws(config)).await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+
+    // 읽지 않는 연결은 pong도 보내지 않습니다.
+    let _silent = admin.websocket(&[]).await.unwrap();
+    let mut reading = admin.websocket(&[WsTopic::Items]).await.unwrap();
+    assert!(matches!(next(&mut reading).await, WsMessage::Subscribed { .. }));
+    assert_eq!(websocket_stats(&admin).await["connections"], 2);
+
+    // 읽는 동안 ping에 pong으로 답하므로 이 연결은 유지됩니다.
+    assert!(tokio::time::timeout(Duration::from_secs(4), reading.next()).await.is_err());
+    let stats = websocket_stats(&admin).await;
+    assert_eq!(stats["connections"], 1);
+    assert_eq!(stats["timed_out"], 1);
+}
+
+#[tokio::test]
+async fn pong_timeout_does_not_wait_for_the_next_ping() {
+    let config = WsConfig { ping_interval: Duration::from_secs(1), pong_timeout: Duration::from_millis(300), ..ws_config() };
+    let app = test_app_with(with_ws(config)).await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+
+    let _silent = admin.websocket(&[]).await.unwrap();
+    // 첫 ping(1초) + pong 제한(0.3초) 뒤에 닫히고, 두 번째 ping(2초)까지 기다리지 않습니다.
+    tokio::time::sleep(Duration::from_millis(1800)).await;
+    let stats = websocket_stats(&admin).await;
+    assert_eq!(stats["connections"], 0);
+    assert_eq!(stats["timed_out"], 1);
+}
+
+#[tokio::test]
+async fn connection_limit_and_shutdown() {
+    let app = test_app_with(with_ws(WsConfig { max_connections: 1, ..ws_config() })).await;
+    let server = app.spawn().await;
+    let admin = Client::builder(&server.base_url).admin_key(ADMIN_KEY).build().unwrap();
+
+    let mut ws = admin.websocket(&[]).await.unwrap();
+    match admin.websocket(&[]).await {
+        Err(Error::Api { status, .. }) => assert_eq!(status, 503),
+        Err(other) => panic!("expected 503, got {:?}", other),
+        Ok(_) => panic!("expected 503"),
+    }
+
+    // 종료 신호를 받으면 close 프레임을 보내고 닫습니다.
+    app.config.app_state.shutdown.cancel();
+    let closed = tokio::time::timeout(Duration::from_secs(5), ws.next()).await.unwrap().unwrap();
+    assert!(closed.is_none());
+}